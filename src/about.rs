@@ -0,0 +1,136 @@
+use std::io::Write;
+use std::path::Path;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub fn build_info() -> String {
+    format!(
+        "{}-{} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        }
+    )
+}
+
+/// 「复制诊断信息」的内容；只包含排查问题所需的状态，不含 token / cookie 等凭据。
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    pub config_path: String,
+    pub config_format: String,
+    pub endpoint: Option<String>,
+    pub proxy: bool,
+    pub doh: bool,
+    pub subscriptions: usize,
+    pub latency_ms: Option<u128>,
+    pub last_error: Option<String>,
+    pub policy_managed: bool,
+}
+
+impl Diagnostics {
+    pub fn report(&self) -> String {
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+        let lines = [
+            format!("rightcode-floatingball {VERSION}"),
+            format!("build: {}", build_info()),
+            format!("config: {} ({})", self.config_path, self.config_format),
+            format!("policy managed: {}", yes_no(self.policy_managed)),
+            format!("endpoint: {}", self.endpoint.as_deref().unwrap_or("-")),
+            format!("proxy: {}", yes_no(self.proxy)),
+            format!("doh: {}", yes_no(self.doh)),
+            format!("subscriptions: {}", self.subscriptions),
+            format!(
+                "latency: {}",
+                self.latency_ms
+                    .map(|ms| format!("{ms} ms"))
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+            format!("last error: {}", self.last_error.as_deref().unwrap_or("-")),
+        ];
+
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// 把若干文件打包成诊断包（zip），`files` 为包内文件名与内容。
+pub fn write_bundle(path: &Path, files: &[(&str, Vec<u8>)]) -> Result<(), BundleError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(*name, options)?;
+        zip.write_all(contents)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::{Diagnostics, VERSION, write_bundle};
+
+    #[test]
+    fn report_includes_version_and_state() {
+        let report = Diagnostics {
+            config_path: "/tmp/config.toml".to_string(),
+            config_format: "toml".to_string(),
+            endpoint: Some("https://right.codes".to_string()),
+            subscriptions: 2,
+            latency_ms: Some(320),
+            ..Diagnostics::default()
+        }
+        .report();
+
+        assert!(report.starts_with(&format!("rightcode-floatingball {VERSION}")));
+        assert!(report.contains("endpoint: https://right.codes"));
+        assert!(report.contains("latency: 320 ms"));
+        assert!(report.contains("last error: -"));
+    }
+
+    #[test]
+    fn writes_bundle_entries() {
+        let path = std::env::temp_dir().join(format!(
+            "rcfb-bundle-test-{}/diagnostics.zip",
+            std::process::id()
+        ));
+        write_bundle(
+            &path,
+            &[
+                ("diagnostics.txt", b"version".to_vec()),
+                ("requests.log", Vec::new()),
+            ],
+        )
+        .expect("writes bundle");
+
+        let mut archive =
+            zip::ZipArchive::new(std::fs::File::open(&path).expect("opens")).expect("reads");
+        assert_eq!(archive.len(), 2);
+        let mut contents = String::new();
+        archive
+            .by_name("diagnostics.txt")
+            .expect("has diagnostics")
+            .read_to_string(&mut contents)
+            .expect("reads entry");
+        assert_eq!(contents, "version");
+
+        let _ = std::fs::remove_dir_all(path.parent().expect("has parent"));
+    }
+}
@@ -1,10 +1,109 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDate;
+use reqwest::StatusCode;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::audit::{AuditLog, AuditRecord};
+use crate::config::{
+    AppConfig, AuditLevel, SubscriptionOrder, is_supported_proxy_url, normalize_bearer_token,
+    normalize_cookie_header_value,
+};
+use crate::doh::{self, DohResolver};
 
-use crate::config::{AppConfig, normalize_bearer_token, normalize_cookie_header_value};
+const SUBSCRIPTIONS_PATH: &str = "/subscriptions/list";
+/// 分页上限，防止接口返回循环的 `next` 时无限请求。
+const MAX_PAGES: usize = 20;
+/// 代理或 DNS 不通时尽快报错。
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// 单个请求（含读取响应体）的上限，服务端停止发送数据时不会一直挂起。
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SubscriptionsResponse {
     pub subscriptions: Vec<Subscription>,
+    /// 下一页链接（绝对地址或相对路径），没有更多数据时为空。
+    #[serde(default)]
+    pub next: Option<String>,
+    /// 以偏移量分页的接口返回的下一页偏移。
+    #[serde(default)]
+    pub next_offset: Option<u64>,
+}
+
+impl SubscriptionsResponse {
+    /// 下一页相对 `base` 的路径；指向其他站点的链接会被忽略，避免把凭据发给第三方。
+    fn next_page_path(&self, base: &str) -> Option<String> {
+        if let Some(next) = self
+            .next
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+        {
+            if let Some(path) = next
+                .strip_prefix(base)
+                .filter(|path| path.starts_with('/') || path.starts_with('?'))
+            {
+                return Some(path.to_string());
+            }
+            if next.contains("://") {
+                return None;
+            }
+            return Some(if next.starts_with('/') {
+                next.to_string()
+            } else {
+                format!("/{next}")
+            });
+        }
+
+        self.next_offset
+            .map(|offset| format!("{SUBSCRIPTIONS_PATH}?offset={offset}"))
+    }
+}
+
+/// 用量汇总接口（如 `/usage/summary`）的响应。
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageSummaryResponse {
+    #[serde(default, alias = "days")]
+    pub daily: Vec<DailyUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    /// 为空表示账户总用量。
+    #[serde(default)]
+    pub subscription: Option<String>,
+    pub used: f64,
+}
+
+/// 用量明细接口（如 `/usage/breakdown`）的响应：按 API key 与按模型拆分的用量。
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UsageBreakdown {
+    #[serde(default, alias = "keys")]
+    pub by_key: Vec<UsageEntry>,
+    #[serde(default, alias = "models")]
+    pub by_model: Vec<UsageEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UsageEntry {
+    #[serde(alias = "key", alias = "model")]
+    pub name: String,
+    pub used: f64,
+    /// 请求次数，接口未返回时为 0。
+    #[serde(default)]
+    pub requests: u64,
+}
+
+/// 用量明细表格的排序列。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageColumn {
+    Name,
+    Used,
+    Requests,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -12,37 +111,434 @@ pub struct Subscription {
     pub name: String,
     pub total_quota: f64,
     pub remaining_quota: f64,
+    /// 额度单位（如 `$`、`¥`、`次`），接口未返回时为空。
+    #[serde(default, alias = "currency")]
+    pub unit: Option<String>,
 }
 
+impl Subscription {
+    pub fn format_remaining(&self) -> String {
+        format_quota(self.remaining_quota, self.unit.as_deref())
+    }
+
+    pub fn format_total(&self) -> String {
+        format_quota(self.total_quota, self.unit.as_deref())
+    }
+
+    /// 各模块测试共用：总额度 100、没有单位的订阅。
+    #[cfg(test)]
+    pub fn fixture(name: &str, remaining_quota: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            total_quota: 100.0,
+            remaining_quota,
+            unit: None,
+        }
+    }
+}
+
+/// 按订阅自身的单位格式化额度：货币符号前置，其余单位（如 `次`）后置且整数不带小数。
+pub fn format_quota(value: f64, unit: Option<&str>) -> String {
+    let Some(unit) = unit.map(str::trim).filter(|unit| !unit.is_empty()) else {
+        return format!("{value:.2}");
+    };
+
+    let is_symbol = unit.chars().count() == 1 && !unit.chars().all(char::is_alphanumeric);
+    if is_symbol {
+        format!("{unit}{value:.2}")
+    } else if value.fract() == 0.0 {
+        format!("{value:.0} {unit}")
+    } else {
+        format!("{value:.2} {unit}")
+    }
+}
+
+/// 成功的拉取结果，附带实际响应的 API 地址。
+#[derive(Debug, Clone)]
+pub struct FetchedSubscriptions {
+    pub subscriptions: Vec<Subscription>,
+    pub endpoint: String,
+    /// 订阅列表超过页数上限时为已拉取的页数，之后的页被跳过。
+    pub truncated_at: Option<usize>,
+}
+
+/// 响应体上限；api_base 配错指向大页面时提前中断，而不是把整页读进内存再解析失败。
+const MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
 #[derive(Debug, thiserror::Error)]
 pub enum FetchError {
     #[error("missing config: bearer token or cf_clearance cookie")]
     MissingConfig,
+    #[error("unsupported proxy url: {0}")]
+    UnsupportedProxy(String),
+    #[error("received an HTML verification page instead of JSON")]
+    Challenge,
+    #[error("response body exceeds {limit} bytes, check api_base")]
+    BodyTooLarge { limit: usize },
+    #[error("invalid response: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("response has no quota at {0}")]
+    MissingField(String),
     #[error("request error: {0}")]
     Request(#[from] reqwest::Error),
+    #[error("plugin: {0}")]
+    Plugin(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchFailureKind {
+    /// Cloudflare 返回了 HTML 验证页，通常是 cf_clearance 失效，需要重新验证。
+    Challenge,
+    /// 接口返回 401/403，Authorization token 无效或已过期。
+    Unauthorized,
+    Other,
+}
+
+/// 可在消息中传递的失败结果（`FetchError` 本身不可 Clone）。
+#[derive(Debug, Clone)]
+pub struct FetchFailure {
+    pub kind: FetchFailureKind,
+    pub message: String,
+    /// 已收到响应时的 HTTP 状态码。
+    pub status: Option<u16>,
+    /// 按错误原因给出的排查建议，显示在设置页的错误面板中。
+    pub suggestions: Vec<&'static str>,
+}
+
+impl From<FetchError> for FetchFailure {
+    fn from(err: FetchError) -> Self {
+        let kind = match &err {
+            FetchError::Challenge => FetchFailureKind::Challenge,
+            FetchError::Request(err)
+                if err.status().is_some_and(|status| {
+                    status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+                }) =>
+            {
+                FetchFailureKind::Unauthorized
+            }
+            _ => FetchFailureKind::Other,
+        };
+        Self {
+            kind,
+            message: err.to_string(),
+            status: failure_status(&err),
+            suggestions: suggestions(&err),
+        }
+    }
+}
+
+fn suggestions(err: &FetchError) -> Vec<&'static str> {
+    match err {
+        FetchError::MissingConfig => {
+            vec!["在「常规」页填写 Authorization token 或 cf_clearance Cookie"]
+        }
+        FetchError::UnsupportedProxy(_) => {
+            vec!["检查「高级」页的代理地址，例如 http://127.0.0.1:7890"]
+        }
+        FetchError::Challenge => vec![
+            "检查 cf_clearance 是否过期：在浏览器中重新通过验证后更新 Cookie",
+            "User-Agent 需与获取 Cookie 的浏览器一致",
+        ],
+        FetchError::BodyTooLarge { .. } | FetchError::Decode(_) => vec![
+            "检查 api_base 是否指向接口地址而不是网页",
+            "接口格式可能已变更，可尝试更新到最新版本",
+        ],
+        FetchError::MissingField(_) => {
+            vec!["检查数据源的字段路径（JSON Pointer，如 /data/remaining）是否与响应一致"]
+        }
+        FetchError::Request(err) => match err.status() {
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+                vec!["检查 Authorization token 是否过期，从控制台重新复制"]
+            }
+            Some(StatusCode::TOO_MANY_REQUESTS) => vec!["请求过于频繁，调大刷新间隔"],
+            Some(status) if status.is_server_error() => {
+                vec!["服务端暂时故障，稍后重试，或在「高级」页配置备用镜像"]
+            }
+            Some(_) => Vec::new(),
+            None if err.is_timeout() => vec!["请求超时：检查网络，或在「高级」页配置代理"],
+            None => vec!["无法连接：检查网络、代理与 DoH 设置"],
+        },
+        FetchError::Plugin(_) => {
+            vec!["在「高级」页的插件列表中试运行，查看插件的错误说明"]
+        }
+    }
+}
+
+/// 共享的 HTTP 客户端（复用连接池），User-Agent 与代理在构建时确定，修改设置后重建。
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    http: reqwest::Client,
+    audit: Option<AuditLog>,
+    audit_level: AuditLevel,
+}
+
+impl ApiClient {
+    pub fn new(config: &AppConfig) -> Result<Self, FetchError> {
+        let user_agent = config.user_agent.trim();
+        let mut builder = reqwest::Client::builder()
+            .user_agent(if user_agent.is_empty() {
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:146.0) Gecko/20100101 Firefox/146.0"
+            } else {
+                user_agent
+            })
+            .gzip(true)
+            .brotli(true)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT);
+
+        let proxy_url = config.proxy_url.trim();
+        if !proxy_url.is_empty() {
+            if !is_supported_proxy_url(proxy_url) {
+                return Err(FetchError::UnsupportedProxy(proxy_url.to_string()));
+            }
+            let no_proxy = reqwest::NoProxy::from_string(&config.no_proxy.join(","));
+            builder = builder.proxy(reqwest::Proxy::all(proxy_url)?.no_proxy(no_proxy));
+        }
+
+        if let Some(endpoint) = doh::endpoint_for(&config.doh) {
+            builder = builder.dns_resolver(Arc::new(DohResolver::new(endpoint)?));
+        }
+
+        Ok(Self {
+            http: builder.build()?,
+            audit: None,
+            audit_level: config.audit_level,
+        })
+    }
+
+    /// 中继推送、错误上报等非查询请求同样走代理与 DoH 设置。
+    pub fn post(&self, url: &str) -> reqwest::RequestBuilder {
+        self.http.post(url)
+    }
+
+    /// 把每次请求的结果写入请求日志。
+    pub fn with_audit(mut self, log: AuditLog) -> Self {
+        self.audit = Some(log);
+        self
+    }
+
+    fn audit(
+        &self,
+        url: &str,
+        started: Instant,
+        outcome: Result<(u16, Option<usize>), &FetchError>,
+    ) {
+        let Some(log) = &self.audit else {
+            return;
+        };
+        let (status, bytes, error) = match outcome {
+            Ok((status, bytes)) => (Some(status), bytes, None),
+            Err(err) => (failure_status(err), None, Some(audit_error(err))),
+        };
+        log.record(
+            self.audit_level,
+            AuditRecord::new(url, status, started.elapsed(), bytes, error),
+        );
+    }
 }
 
-pub async fn fetch_subscriptions(config: &AppConfig) -> Result<SubscriptionsResponse, FetchError> {
+fn failure_status(err: &FetchError) -> Option<u16> {
+    match err {
+        FetchError::Request(err) => err.status().map(|status| status.as_u16()),
+        _ => None,
+    }
+}
+
+/// 日志中的简短错误描述；不用 `Display`，因为请求错误会带上完整地址。
+fn audit_error(err: &FetchError) -> String {
+    match err {
+        FetchError::Challenge => "验证页".to_string(),
+        FetchError::BodyTooLarge { .. } => "响应过大".to_string(),
+        FetchError::Decode(_) | FetchError::MissingField(_) => "解析失败".to_string(),
+        FetchError::Request(err) => match err.status() {
+            Some(status) => format!("HTTP {}", status.as_u16()),
+            None if err.is_timeout() => "超时".to_string(),
+            None if err.is_connect() => "连接失败".to_string(),
+            None => "请求失败".to_string(),
+        },
+        FetchError::MissingConfig | FetchError::UnsupportedProxy(_) => "配置错误".to_string(),
+        FetchError::Plugin(_) => "插件错误".to_string(),
+    }
+}
+
+/// 依次尝试主地址与备用地址，上次成功的地址（`preferred_endpoint`）优先。
+pub async fn fetch_subscriptions(
+    client: &ApiClient,
+    config: &AppConfig,
+    preferred_endpoint: Option<&str>,
+) -> Result<FetchedSubscriptions, FetchError> {
     if config.bearer_token.trim().is_empty() || config.cookie.trim().is_empty() {
         return Err(FetchError::MissingConfig);
     }
 
-    let user_agent = config.user_agent.trim();
-    let client = reqwest::Client::builder()
-        .user_agent(if user_agent.is_empty() {
-            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:146.0) Gecko/20100101 Firefox/146.0"
-        } else {
-            user_agent
-        })
-        .build()?;
+    let endpoints = ordered_endpoints(config.api_endpoints(), preferred_endpoint);
+
+    let mut last_error = None;
+    for endpoint in endpoints {
+        match fetch_all_pages(client, config, &endpoint).await {
+            Ok((subscriptions, truncated_at)) => {
+                return Ok(FetchedSubscriptions {
+                    subscriptions,
+                    endpoint,
+                    truncated_at,
+                });
+            }
+            Err(err) => last_error = Some(err),
+        }
+    }
 
-    let base = config.api_base.trim_end_matches('/');
-    let url = format!("{base}/subscriptions/list");
+    Err(last_error.unwrap_or(FetchError::MissingConfig))
+}
+
+fn ordered_endpoints(mut endpoints: Vec<String>, preferred: Option<&str>) -> Vec<String> {
+    if let Some(index) = preferred.and_then(|p| endpoints.iter().position(|e| e == p)) {
+        let preferred = endpoints.remove(index);
+        endpoints.insert(0, preferred);
+    }
+    endpoints
+}
+
+async fn fetch_all_pages(
+    client: &ApiClient,
+    config: &AppConfig,
+    base: &str,
+) -> Result<(Vec<Subscription>, Option<usize>), FetchError> {
+    let mut subscriptions = Vec::new();
+    let mut path = SUBSCRIPTIONS_PATH.to_string();
+
+    for _ in 0..MAX_PAGES {
+        let page = get_json::<SubscriptionsResponse>(client, config, base, &path).await?;
+        let next = page.next_page_path(base);
+        let empty = page.subscriptions.is_empty();
+        subscriptions.extend(page.subscriptions);
+
+        match next {
+            Some(next) if !empty && next != path => path = next,
+            _ => return Ok((subscriptions, None)),
+        }
+    }
+
+    Ok((subscriptions, Some(MAX_PAGES)))
+}
+
+/// 拉取按日用量汇总；`base` 为当前可用的 API 地址。
+pub async fn fetch_usage_summary(
+    client: &ApiClient,
+    config: &AppConfig,
+    base: &str,
+) -> Result<Vec<DailyUsage>, FetchError> {
+    if config.bearer_token.trim().is_empty() || config.cookie.trim().is_empty() {
+        return Err(FetchError::MissingConfig);
+    }
+
+    let path = format!(
+        "/{}",
+        config.usage_summary_path.trim().trim_start_matches('/')
+    );
+    let response = get_json::<UsageSummaryResponse>(client, config, base, &path).await?;
+    Ok(response.daily)
+}
+
+/// 拉取按 API key / 模型拆分的用量明细；`base` 为当前可用的 API 地址。
+pub async fn fetch_usage_breakdown(
+    client: &ApiClient,
+    config: &AppConfig,
+    base: &str,
+) -> Result<UsageBreakdown, FetchError> {
+    if config.bearer_token.trim().is_empty() || config.cookie.trim().is_empty() {
+        return Err(FetchError::MissingConfig);
+    }
+
+    let path = format!(
+        "/{}",
+        config.usage_breakdown_path.trim().trim_start_matches('/')
+    );
+    get_json::<UsageBreakdown>(client, config, base, &path).await
+}
+
+/// 订阅列表首页的原始响应体，供命令行生成诊断包时使用。
+pub async fn fetch_subscriptions_raw(
+    client: &ApiClient,
+    config: &AppConfig,
+) -> Result<Vec<u8>, FetchError> {
+    if config.bearer_token.trim().is_empty() || config.cookie.trim().is_empty() {
+        return Err(FetchError::MissingConfig);
+    }
+
+    let base = config
+        .api_endpoints()
+        .into_iter()
+        .next()
+        .ok_or(FetchError::MissingConfig)?;
+    let response = authorized_get(client, config, &base, SUBSCRIPTIONS_PATH).await?;
+    read_limited(response, MAX_RESPONSE_BYTES).await
+}
+
+/// 启动时的轻量凭证检查：只请求订阅列表首页并检查状态码，不读取响应体。
+pub async fn probe_credentials(client: &ApiClient, config: &AppConfig) -> Result<(), FetchError> {
+    if config.bearer_token.trim().is_empty() || config.cookie.trim().is_empty() {
+        return Err(FetchError::MissingConfig);
+    }
+
+    let base = config
+        .api_endpoints()
+        .into_iter()
+        .next()
+        .ok_or(FetchError::MissingConfig)?;
+    let started = Instant::now();
+    let result = authorized_get(client, config, &base, SUBSCRIPTIONS_PATH).await;
+    client.audit(
+        &format!("{base}{SUBSCRIPTIONS_PATH}"),
+        started,
+        result
+            .as_ref()
+            .map(|response| (response.status().as_u16(), None)),
+    );
+    result?;
+    Ok(())
+}
+
+async fn get_json<T: DeserializeOwned>(
+    client: &ApiClient,
+    config: &AppConfig,
+    base: &str,
+    path: &str,
+) -> Result<T, FetchError> {
+    let started = Instant::now();
+    let result: Result<(StatusCode, Vec<u8>), FetchError> = async {
+        let response = authorized_get(client, config, base, path).await?;
+        let status = response.status();
+        Ok((status, read_limited(response, MAX_RESPONSE_BYTES).await?))
+    }
+    .await;
+    client.audit(
+        &format!("{base}{path}"),
+        started,
+        result
+            .as_ref()
+            .map(|(status, body)| (status.as_u16(), Some(body.len()))),
+    );
+    let (_, body) = result?;
+    if let Some(log) = &client.audit {
+        log.set_last_response(&body);
+    }
+    Ok(serde_json::from_slice::<T>(&body)?)
+}
+
+/// 带认证信息发起 GET，验证页与错误状态码转为对应的错误。
+async fn authorized_get(
+    client: &ApiClient,
+    config: &AppConfig,
+    base: &str,
+    path: &str,
+) -> Result<reqwest::Response, FetchError> {
+    let url = format!("{base}{path}");
 
     let token = normalize_bearer_token(&config.bearer_token);
     let cookie = normalize_cookie_header_value(&config.cookie);
 
     let response = client
+        .http
         .get(url)
         .header("Accept", "*/*")
         .header("Referer", format!("{base}/dashboard"))
@@ -50,24 +546,259 @@ pub async fn fetch_subscriptions(config: &AppConfig) -> Result<SubscriptionsResp
         .header("Authorization", token)
         .header("Cookie", cookie)
         .send()
-        .await?
-        .error_for_status()?;
+        .await?;
 
-    Ok(response.json::<SubscriptionsResponse>().await?)
+    // 验证页常伴随 403/503，需在检查状态码之前识别
+    if is_html_response(&response) {
+        return Err(FetchError::Challenge);
+    }
+    Ok(response.error_for_status()?)
 }
 
-pub fn default_subscription_index(
+/// 不带认证信息地读取第三方 JSON（如主题索引），仍走代理/DoH 设置与响应体上限。
+pub async fn fetch_public_json<T: DeserializeOwned>(
+    client: &ApiClient,
+    url: &str,
+) -> Result<T, FetchError> {
+    let headers = BTreeMap::from([("Accept".to_string(), "application/json".to_string())]);
+    let body = fetch_raw(client, url, &headers).await?;
+    Ok(serde_json::from_slice::<T>(&body)?)
+}
+
+/// 以指定请求头读取原始响应体（供插件使用），同样受响应体上限约束。
+pub async fn fetch_raw(
+    client: &ApiClient,
+    url: &str,
+    headers: &BTreeMap<String, String>,
+) -> Result<Vec<u8>, FetchError> {
+    let mut request = client.http.get(url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let started = Instant::now();
+    let result: Result<(StatusCode, Vec<u8>), FetchError> = async {
+        let response = request.send().await?.error_for_status()?;
+        let status = response.status();
+        Ok((status, read_limited(response, MAX_RESPONSE_BYTES).await?))
+    }
+    .await;
+    client.audit(
+        url,
+        started,
+        result
+            .as_ref()
+            .map(|(status, body)| (status.as_u16(), Some(body.len()))),
+    );
+    result.map(|(_, body)| body)
+}
+
+/// 只取响应头（如速率限制信息），不读取响应体。
+pub async fn fetch_headers(
+    client: &ApiClient,
+    url: &str,
+    headers: &BTreeMap<String, String>,
+) -> Result<reqwest::header::HeaderMap, FetchError> {
+    let mut request = client.http.get(url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let started = Instant::now();
+    let result: Result<reqwest::Response, FetchError> = async {
+        let response = request.send().await?;
+        Ok(response.error_for_status()?)
+    }
+    .await;
+    client.audit(
+        url,
+        started,
+        result
+            .as_ref()
+            .map(|response| (response.status().as_u16(), None)),
+    );
+    result.map(|response| response.headers().clone())
+}
+
+fn is_html_response(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(is_html_content_type)
+}
+
+fn is_html_content_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/html"))
+}
+
+async fn read_limited(
+    mut response: reqwest::Response,
+    limit: usize,
+) -> Result<Vec<u8>, FetchError> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(FetchError::BodyTooLarge { limit });
+    }
+
+    // 压缩响应的 Content-Length 是压缩后大小，仍需按解压后的实际字节数计数
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        append_limited(&mut body, &chunk, limit)?;
+    }
+    Ok(body)
+}
+
+fn append_limited(body: &mut Vec<u8>, chunk: &[u8], limit: usize) -> Result<(), FetchError> {
+    if body.len() + chunk.len() > limit {
+        return Err(FetchError::BodyTooLarge { limit });
+    }
+    body.extend_from_slice(chunk);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DataAnomaly {
+    #[error("quota is not a finite number")]
+    NotFinite,
+    #[error("quota is negative")]
+    Negative,
+    #[error("remaining quota exceeds total quota")]
+    RemainingExceedsTotal,
+}
+
+/// 校验接口返回的额度是否合理，避免异常数据被静默渲染成 0% 或溢出的水位。
+pub fn validate_subscription(subscription: &Subscription) -> Result<(), DataAnomaly> {
+    let (total, remaining) = (subscription.total_quota, subscription.remaining_quota);
+
+    if !total.is_finite() || !remaining.is_finite() {
+        return Err(DataAnomaly::NotFinite);
+    }
+    if total < 0.0 || remaining < 0.0 {
+        return Err(DataAnomaly::Negative);
+    }
+    // 允许浮点误差
+    if remaining > total + 1e-6 {
+        return Err(DataAnomaly::RemainingExceedsTotal);
+    }
+    Ok(())
+}
+
+/// 按配置的顺序原地排序（稳定排序，相同值保持接口顺序）。
+pub fn sort_subscriptions(subscriptions: &mut [Subscription], order: SubscriptionOrder) {
+    match order {
+        SubscriptionOrder::Api => {}
+        SubscriptionOrder::Remaining => {
+            subscriptions.sort_by(|a, b| b.remaining_quota.total_cmp(&a.remaining_quota))
+        }
+        SubscriptionOrder::Name => subscriptions.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+}
+
+/// 按列原地排序（稳定排序），`descending` 为 `true` 时从大到小。
+pub fn sort_usage_entries(entries: &mut [UsageEntry], column: UsageColumn, descending: bool) {
+    entries.sort_by(|a, b| {
+        let ordering = match column {
+            UsageColumn::Name => a.name.cmp(&b.name),
+            UsageColumn::Used => a.used.total_cmp(&b.used),
+            UsageColumn::Requests => a.requests.cmp(&b.requests),
+        };
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+/// 首选订阅名的最低相似度，低于此值视为没有匹配。
+pub const PREFERRED_MATCH_THRESHOLD: f64 = 0.6;
+
+/// 订阅名相似度（0-1），忽略大小写与首尾空白：完全相同为 1，互相包含时不低于 0.8，
+/// 其余按编辑距离计算。服务商给套餐改名（如加上后缀）后仍能匹配。
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let a = a.trim().to_lowercase();
+    let b = b.trim().to_lowercase();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    let longest = len_a.max(len_b) as f64;
+    if a.contains(&b) || b.contains(&a) {
+        return 0.8 + 0.2 * len_a.min(len_b) as f64 / longest;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                diagonal.min(above).min(row[j]) + 1
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// 与首选名最相似的订阅及其相似度；相同分数取靠前的。
+pub fn match_preferred_name(
     subscriptions: &[Subscription],
     preferred_name: &str,
-) -> Option<usize> {
-    let preferred = subscriptions
+) -> Option<(usize, f64)> {
+    subscriptions
         .iter()
-        .position(|s| s.name.trim() == preferred_name.trim() && s.remaining_quota > 0.0);
+        .enumerate()
+        .map(|(index, s)| (index, name_similarity(&s.name, preferred_name)))
+        .filter(|(_, score)| *score >= PREFERRED_MATCH_THRESHOLD)
+        .fold(None, |best: Option<(usize, f64)>, candidate| match best {
+            Some(best) if best.1 >= candidate.1 => Some(best),
+            _ => Some(candidate),
+        })
+}
+
+/// `subscriptions` 需已按 `order` 排序：依次匹配 `preferred_names`，取第一个仍有余额的订阅；
+/// 都没有时按名称排序取第一个有余额的，其余顺序取剩余额度最多的（接口顺序本身不代表偏好）。
+pub fn default_subscription_index(
+    subscriptions: &[Subscription],
+    preferred_names: &[String],
+    order: SubscriptionOrder,
+) -> Option<usize> {
+    let preferred = preferred_names.iter().find_map(|name| {
+        match_preferred_name(subscriptions, name)
+            .map(|(index, _)| index)
+            .filter(|&index| subscriptions[index].remaining_quota > 0.0)
+    });
 
     if preferred.is_some() {
         return preferred;
     }
 
+    if order == SubscriptionOrder::Name
+        && let Some(index) = subscriptions
+            .iter()
+            .position(|s| s.total_quota > 0.0 && s.remaining_quota > 0.0)
+    {
+        return Some(index);
+    }
+
     subscriptions
         .iter()
         .enumerate()
@@ -80,6 +811,53 @@ pub fn default_subscription_index(
         .map(|(index, _)| index)
 }
 
+/// 当前订阅（`current`）用完后改为显示的订阅：按首选优先级与排列顺序挑选仍有余额的，
+/// 都用完时返回 `None`。
+pub fn fallback_subscription_index(
+    subscriptions: &[Subscription],
+    preferred_names: &[String],
+    order: SubscriptionOrder,
+    current: usize,
+) -> Option<usize> {
+    default_subscription_index(subscriptions, preferred_names, order)
+        .filter(|&index| index != current && subscriptions[index].remaining_quota > 0.0)
+}
+
+/// 手动校正的剩余额度（接口滞后时以控制台为准），接口返回的值变化后自动失效。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManualOverride {
+    pub subscription: String,
+    pub remaining: f64,
+    /// 校正时接口返回的剩余额度。
+    pub baseline: f64,
+}
+
+impl ManualOverride {
+    pub fn new(subscription: &Subscription, remaining: f64) -> Self {
+        Self {
+            subscription: subscription.name.clone(),
+            remaining,
+            baseline: subscription.remaining_quota,
+        }
+    }
+
+    /// 校正后的订阅；不是同一个订阅时返回 `None`。
+    pub fn apply(&self, subscription: &Subscription) -> Option<Subscription> {
+        (subscription.name == self.subscription).then(|| Subscription {
+            remaining_quota: self.remaining,
+            ..subscription.clone()
+        })
+    }
+
+    /// 新数据中该订阅的剩余额度已与校正时不同（或订阅已不存在）。
+    pub fn is_superseded_by(&self, subscriptions: &[Subscription]) -> bool {
+        subscriptions
+            .iter()
+            .find(|sub| sub.name == self.subscription)
+            .is_none_or(|sub| (sub.remaining_quota - self.baseline).abs() > f64::EPSILON)
+    }
+}
+
 pub fn remaining_ratio(subscription: &Subscription) -> f32 {
     if subscription.total_quota <= 0.0 {
         return 0.0;
@@ -87,3 +865,436 @@ pub fn remaining_ratio(subscription: &Subscription) -> f32 {
     let ratio = subscription.remaining_quota / subscription.total_quota;
     ratio.clamp(0.0, 1.0) as f32
 }
+
+/// 对水位比例做指数移动平均；`alpha` 不在 (0, 1) 内时直接使用新值。
+pub fn smooth_ratio(previous: Option<f32>, sample: f32, alpha: f32) -> f32 {
+    match previous {
+        Some(previous) if alpha > 0.0 && alpha < 1.0 => previous + alpha * (sample - previous),
+        _ => sample,
+    }
+}
+
+const LATENCY_WINDOW: usize = 10;
+const LATENCY_GOOD_MS: u64 = 500;
+const LATENCY_SLOW_MS: u64 = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchHealth {
+    Good,
+    Slow,
+    Bad,
+}
+
+/// 最近若干次请求的耗时，用于计算滚动平均延迟与健康状态。
+#[derive(Debug, Clone, Default)]
+pub struct LatencyWindow {
+    samples: VecDeque<Duration>,
+    last: Option<Duration>,
+    last_failed: bool,
+}
+
+impl LatencyWindow {
+    pub fn record(&mut self, elapsed: Duration, success: bool) {
+        self.last = Some(elapsed);
+        self.last_failed = !success;
+        if !success {
+            return;
+        }
+        if self.samples.len() == LATENCY_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+    }
+
+    pub fn last(&self) -> Option<Duration> {
+        self.last
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let total: Duration = self.samples.iter().sum();
+        Some(total / self.samples.len() as u32)
+    }
+
+    pub fn health(&self) -> Option<FetchHealth> {
+        if self.last_failed {
+            return Some(FetchHealth::Bad);
+        }
+        let average = self.average()?.as_millis() as u64;
+        Some(if average < LATENCY_GOOD_MS {
+            FetchHealth::Good
+        } else if average < LATENCY_SLOW_MS {
+            FetchHealth::Slow
+        } else {
+            FetchHealth::Bad
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        DataAnomaly, FetchError, FetchFailure, FetchFailureKind, FetchHealth, LatencyWindow,
+        ManualOverride, SUBSCRIPTIONS_PATH, Subscription, SubscriptionsResponse, UsageBreakdown,
+        UsageColumn, UsageEntry, UsageSummaryResponse, append_limited, default_subscription_index,
+        fallback_subscription_index, format_quota, is_html_content_type, match_preferred_name,
+        name_similarity, ordered_endpoints, smooth_ratio, sort_subscriptions, sort_usage_entries,
+        validate_subscription,
+    };
+    use crate::config::SubscriptionOrder;
+
+    fn quota(total_quota: f64, remaining_quota: f64) -> Subscription {
+        Subscription {
+            total_quota,
+            ..Subscription::fixture("test", remaining_quota)
+        }
+    }
+
+    fn names(subscriptions: &[Subscription]) -> Vec<&str> {
+        subscriptions.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn sorts_subscriptions_by_configured_order() {
+        let api = vec![
+            Subscription::fixture("b", 10.0),
+            Subscription::fixture("c", 50.0),
+            Subscription::fixture("a", 10.0),
+        ];
+
+        let mut sorted = api.clone();
+        sort_subscriptions(&mut sorted, SubscriptionOrder::Api);
+        assert_eq!(names(&sorted), ["b", "c", "a"]);
+
+        sort_subscriptions(&mut sorted, SubscriptionOrder::Remaining);
+        assert_eq!(names(&sorted), ["c", "b", "a"]);
+
+        sort_subscriptions(&mut sorted, SubscriptionOrder::Name);
+        assert_eq!(names(&sorted), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn default_index_follows_order_when_preferred_is_missing() {
+        let subscriptions = [
+            Subscription::fixture("a", 0.0),
+            Subscription::fixture("b", 10.0),
+            Subscription::fixture("c", 50.0),
+        ];
+        let preferred = |name: &str| vec![name.to_string()];
+        assert_eq!(
+            default_subscription_index(&subscriptions, &preferred("b"), SubscriptionOrder::Api),
+            Some(1)
+        );
+        assert_eq!(
+            default_subscription_index(&subscriptions, &preferred("x"), SubscriptionOrder::Api),
+            Some(2)
+        );
+        assert_eq!(
+            default_subscription_index(&subscriptions, &preferred("x"), SubscriptionOrder::Name),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn matches_renamed_preferred_subscription() {
+        assert_eq!(name_similarity(" 小股东套餐 ", "小股东套餐"), 1.0);
+        assert!(name_similarity("小股东套餐 Pro", "小股东套餐") >= 0.8);
+        assert!(name_similarity("小股东套餐", "小股东计划") >= 0.6);
+        assert!(name_similarity("Team Plan", "小股东套餐") < 0.6);
+        assert_eq!(name_similarity("", "小股东套餐"), 0.0);
+
+        let subscriptions = [
+            Subscription::fixture("Team Plan", 10.0),
+            Subscription::fixture("小股东套餐（2025）", 5.0),
+        ];
+        assert_eq!(
+            match_preferred_name(&subscriptions, "小股东套餐").map(|(index, _)| index),
+            Some(1)
+        );
+        assert_eq!(match_preferred_name(&subscriptions, "企业版"), None);
+        assert_eq!(
+            default_subscription_index(
+                &subscriptions,
+                &["小股东套餐".to_string()],
+                SubscriptionOrder::Api
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn fails_over_to_next_preferred_subscription() {
+        let subscriptions = [
+            Subscription::fixture("Plan A", 0.0),
+            Subscription::fixture("Plan B", 5.0),
+            Subscription::fixture("Plan C", 50.0),
+        ];
+        let preferred = ["Plan A".to_string(), "Plan B".to_string()];
+        assert_eq!(
+            default_subscription_index(&subscriptions, &preferred, SubscriptionOrder::Api),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn falls_back_only_to_subscriptions_with_quota() {
+        let preferred = ["Plan A".to_string()];
+        let subscriptions = [
+            Subscription::fixture("Plan A", 0.0),
+            Subscription::fixture("Plan B", 5.0),
+        ];
+        assert_eq!(
+            fallback_subscription_index(&subscriptions, &preferred, SubscriptionOrder::Api, 0),
+            Some(1)
+        );
+        let depleted = [
+            Subscription::fixture("Plan A", 0.0),
+            Subscription::fixture("Plan B", 0.0),
+        ];
+        assert_eq!(
+            fallback_subscription_index(&depleted, &preferred, SubscriptionOrder::Api, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn formats_quota_with_subscription_unit() {
+        assert_eq!(format_quota(12.5, None), "12.50");
+        assert_eq!(format_quota(12.5, Some("$")), "$12.50");
+        assert_eq!(format_quota(120.0, Some("次")), "120 次");
+        assert_eq!(format_quota(1.25, Some("credits")), "1.25 credits");
+    }
+
+    fn page(next: Option<&str>, next_offset: Option<u64>) -> SubscriptionsResponse {
+        SubscriptionsResponse {
+            subscriptions: Vec::new(),
+            next: next.map(str::to_string),
+            next_offset,
+        }
+    }
+
+    #[test]
+    fn resolves_next_page_links() {
+        let base = "https://right.codes";
+        assert_eq!(page(None, None).next_page_path(base), None);
+        assert_eq!(
+            page(Some("https://right.codes/subscriptions/list?page=2"), None).next_page_path(base),
+            Some("/subscriptions/list?page=2".to_string())
+        );
+        assert_eq!(
+            page(Some("subscriptions/list?page=3"), None).next_page_path(base),
+            Some("/subscriptions/list?page=3".to_string())
+        );
+        assert_eq!(
+            page(None, Some(50)).next_page_path(base),
+            Some(format!("{SUBSCRIPTIONS_PATH}?offset=50"))
+        );
+    }
+
+    #[test]
+    fn ignores_next_links_to_other_hosts() {
+        assert_eq!(
+            page(Some("https://evil.example/steal"), None).next_page_path("https://right.codes"),
+            None
+        );
+        assert_eq!(
+            page(Some("https://right.codes.evil.example/steal"), None)
+                .next_page_path("https://right.codes"),
+            None
+        );
+    }
+
+    #[test]
+    fn deserializes_usage_summary() {
+        let raw = r#"{"daily":[
+            {"date":"2026-10-01","used":3.5},
+            {"date":"2026-10-02","used":1,"subscription":"plan"}
+        ]}"#;
+        let response = serde_json::from_str::<UsageSummaryResponse>(raw).expect("valid json");
+        assert_eq!(response.daily.len(), 2);
+        assert_eq!(response.daily[0].subscription, None);
+        assert_eq!(response.daily[1].used, 1.0);
+    }
+
+    #[test]
+    fn deserializes_and_sorts_usage_breakdown() {
+        let raw = r#"{"keys":[
+            {"key":"sk-a…1f","used":3.5,"requests":40},
+            {"key":"sk-b…9c","used":12},
+            {"key":"sk-c…07","used":0.5,"requests":90}
+        ],"by_model":[{"model":"claude-sonnet","used":16}]}"#;
+        let mut breakdown = serde_json::from_str::<UsageBreakdown>(raw).expect("valid json");
+        assert_eq!(breakdown.by_model[0].name, "claude-sonnet");
+        assert_eq!(breakdown.by_key[1].requests, 0);
+
+        let names =
+            |entries: &[UsageEntry]| entries.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+        sort_usage_entries(&mut breakdown.by_key, UsageColumn::Used, true);
+        assert_eq!(names(&breakdown.by_key), ["sk-b…9c", "sk-a…1f", "sk-c…07"]);
+        sort_usage_entries(&mut breakdown.by_key, UsageColumn::Requests, true);
+        assert_eq!(names(&breakdown.by_key), ["sk-c…07", "sk-a…1f", "sk-b…9c"]);
+        sort_usage_entries(&mut breakdown.by_key, UsageColumn::Name, false);
+        assert_eq!(names(&breakdown.by_key), ["sk-a…1f", "sk-b…9c", "sk-c…07"]);
+    }
+
+    #[test]
+    fn deserializes_optional_unit() {
+        let raw = r#"{"subscriptions":[
+            {"name":"a","total_quota":10,"remaining_quota":5,"currency":"¥"},
+            {"name":"b","total_quota":10,"remaining_quota":5}
+        ]}"#;
+        let response = serde_json::from_str::<SubscriptionsResponse>(raw).expect("valid json");
+        assert_eq!(response.subscriptions[0].unit.as_deref(), Some("¥"));
+        assert_eq!(response.subscriptions[1].unit, None);
+    }
+
+    #[test]
+    fn detects_html_challenge_pages() {
+        assert!(is_html_content_type("text/html; charset=UTF-8"));
+        assert!(is_html_content_type("Text/HTML"));
+        assert!(!is_html_content_type("application/json"));
+    }
+
+    #[test]
+    fn classifies_challenge_failures() {
+        let failure = FetchFailure::from(FetchError::Challenge);
+        assert_eq!(failure.kind, FetchFailureKind::Challenge);
+
+        let failure = FetchFailure::from(FetchError::MissingConfig);
+        assert_eq!(failure.kind, FetchFailureKind::Other);
+    }
+
+    #[test]
+    fn suggests_fixes_by_cause() {
+        let failure = FetchFailure::from(FetchError::Challenge);
+        assert_eq!(failure.status, None);
+        assert!(failure.suggestions[0].contains("cf_clearance"));
+
+        let failure = FetchFailure::from(FetchError::BodyTooLarge { limit: 8 });
+        assert!(failure.suggestions[0].contains("api_base"));
+    }
+
+    #[test]
+    fn caps_response_body_size() {
+        let mut body = Vec::new();
+        assert!(append_limited(&mut body, b"hello", 8).is_ok());
+        assert!(matches!(
+            append_limited(&mut body, b"world", 8),
+            Err(FetchError::BodyTooLarge { limit: 8 })
+        ));
+        assert_eq!(body, b"hello");
+    }
+
+    #[test]
+    fn remembered_endpoint_is_tried_first() {
+        let endpoints = vec!["https://a".to_string(), "https://b".to_string()];
+        assert_eq!(
+            ordered_endpoints(endpoints.clone(), Some("https://b")),
+            vec!["https://b", "https://a"]
+        );
+        assert_eq!(
+            ordered_endpoints(endpoints.clone(), Some("https://gone")),
+            endpoints
+        );
+    }
+
+    #[test]
+    fn accepts_sane_quotas() {
+        assert_eq!(validate_subscription(&quota(100.0, 40.0)), Ok(()));
+        assert_eq!(validate_subscription(&quota(0.0, 0.0)), Ok(()));
+    }
+
+    #[test]
+    fn rejects_nonsense_quotas() {
+        assert_eq!(
+            validate_subscription(&quota(f64::NAN, 1.0)),
+            Err(DataAnomaly::NotFinite)
+        );
+        assert_eq!(
+            validate_subscription(&quota(100.0, -1.0)),
+            Err(DataAnomaly::Negative)
+        );
+        assert_eq!(
+            validate_subscription(&quota(100.0, 150.0)),
+            Err(DataAnomaly::RemainingExceedsTotal)
+        );
+    }
+
+    #[test]
+    fn smoothing_moves_toward_sample() {
+        assert_eq!(smooth_ratio(None, 0.8, 0.5), 0.8);
+        assert!((smooth_ratio(Some(0.4), 0.8, 0.5) - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn smoothing_disabled_uses_raw_sample() {
+        assert_eq!(smooth_ratio(Some(0.4), 0.8, 0.0), 0.8);
+        assert_eq!(smooth_ratio(Some(0.4), 0.8, 1.0), 0.8);
+    }
+
+    #[test]
+    fn empty_window_has_no_health() {
+        assert_eq!(LatencyWindow::default().health(), None);
+    }
+
+    #[test]
+    fn classifies_by_rolling_average() {
+        let mut window = LatencyWindow::default();
+        window.record(Duration::from_millis(200), true);
+        window.record(Duration::from_millis(400), true);
+        assert_eq!(window.average(), Some(Duration::from_millis(300)));
+        assert_eq!(window.health(), Some(FetchHealth::Good));
+
+        window.record(Duration::from_millis(3000), true);
+        assert_eq!(window.health(), Some(FetchHealth::Slow));
+    }
+
+    #[test]
+    fn failure_marks_unhealthy_until_next_success() {
+        let mut window = LatencyWindow::default();
+        window.record(Duration::from_millis(100), true);
+        window.record(Duration::from_millis(100), false);
+        assert_eq!(window.health(), Some(FetchHealth::Bad));
+        assert_eq!(window.average(), Some(Duration::from_millis(100)));
+
+        window.record(Duration::from_millis(100), true);
+        assert_eq!(window.health(), Some(FetchHealth::Good));
+    }
+
+    #[test]
+    fn keeps_only_recent_samples() {
+        let mut window = LatencyWindow::default();
+        for _ in 0..20 {
+            window.record(Duration::from_millis(5000), true);
+        }
+        for _ in 0..10 {
+            window.record(Duration::from_millis(100), true);
+        }
+        assert_eq!(window.average(), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn manual_override_lasts_until_the_api_value_changes() {
+        let sub = |name: &str, remaining: f64| Subscription {
+            name: name.to_string(),
+            total_quota: 100.0,
+            remaining_quota: remaining,
+            unit: None,
+        };
+        let correction = ManualOverride::new(&sub("Pro", 80.0), 65.0);
+
+        assert_eq!(
+            correction.apply(&sub("Pro", 80.0)).unwrap().remaining_quota,
+            65.0
+        );
+        assert!(correction.apply(&sub("Team", 80.0)).is_none());
+
+        assert!(!correction.is_superseded_by(&[sub("Team", 10.0), sub("Pro", 80.0)]));
+        assert!(correction.is_superseded_by(&[sub("Pro", 64.0)]));
+        assert!(correction.is_superseded_by(&[sub("Team", 10.0)]));
+    }
+}
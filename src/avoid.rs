@@ -0,0 +1,180 @@
+use std::time::{Duration, Instant};
+
+use iced::{Point, Rectangle, Size};
+
+/// 被拖动的窗口停下多久后悬浮球回到原位。
+const RETURN_DELAY: Duration = Duration::from_secs(1);
+/// 避让时与屏幕边缘保持的距离（逻辑像素）。
+const EDGE_MARGIN: f32 = 16.0;
+
+/// 智能避让：其他窗口被拖到悬浮球下方时，悬浮球挪到最近的空闲角落，拖动结束后返回。
+#[derive(Debug, Clone, Default)]
+pub struct Avoider {
+    last_foreground: Option<Rectangle>,
+    /// 避让中时记录原位置，以及被拖动窗口停下的时刻。
+    avoiding: Option<(Point, Option<Instant>)>,
+}
+
+impl Avoider {
+    pub fn is_avoiding(&self) -> bool {
+        self.avoiding.is_some()
+    }
+
+    /// 根据前台窗口的位置推进状态，返回悬浮球需要移动到的位置。
+    pub fn update(
+        &mut self,
+        foreground: Option<Rectangle>,
+        ball: Rectangle,
+        work_area: Rectangle,
+        now: Instant,
+    ) -> Option<Point> {
+        // 与悬浮球同样大小的前台窗口就是悬浮球自己（拖动悬浮球时）
+        let foreground = foreground.filter(|window| !same_size(window.size(), ball.size()));
+        // 位置变化而大小不变，视为正在拖动窗口
+        let dragging = match (self.last_foreground, foreground) {
+            (Some(last), Some(current)) => {
+                last.size() == current.size() && last.position() != current.position()
+            }
+            _ => false,
+        };
+        self.last_foreground = foreground;
+
+        let blocking = foreground.filter(|window| dragging && window.intersects(&ball));
+        match (&mut self.avoiding, blocking) {
+            (None, Some(window)) => {
+                let target = free_corner(window, ball, work_area)?;
+                self.avoiding = Some((ball.position(), None));
+                Some(target)
+            }
+            (Some((_, still_since)), Some(window)) => {
+                *still_since = None;
+                free_corner(window, ball, work_area)
+            }
+            (Some((_, still_since)), None) if dragging => {
+                *still_since = None;
+                None
+            }
+            (Some((home, still_since)), None) => {
+                let since = *still_since.get_or_insert(now);
+                if now.saturating_duration_since(since) < RETURN_DELAY {
+                    return None;
+                }
+                let home = *home;
+                self.avoiding = None;
+                Some(home)
+            }
+            (None, None) => None,
+        }
+    }
+}
+
+fn same_size(a: Size, b: Size) -> bool {
+    (a.width - b.width).abs() < 2.0 && (a.height - b.height).abs() < 2.0
+}
+
+/// 工作区四个角中不与窗口重叠、离悬浮球最近的一个。
+fn free_corner(window: Rectangle, ball: Rectangle, work_area: Rectangle) -> Option<Point> {
+    let size = ball.size();
+    let left = work_area.x + EDGE_MARGIN;
+    let top = work_area.y + EDGE_MARGIN;
+    let right = work_area.x + work_area.width - size.width - EDGE_MARGIN;
+    let bottom = work_area.y + work_area.height - size.height - EDGE_MARGIN;
+
+    [
+        Point::new(left, top),
+        Point::new(right, top),
+        Point::new(left, bottom),
+        Point::new(right, bottom),
+    ]
+    .into_iter()
+    .filter(|corner| !Rectangle::new(*corner, size).intersects(&window))
+    .min_by(|a, b| {
+        a.distance(ball.position())
+            .total_cmp(&b.distance(ball.position()))
+    })
+}
+
+/// 窗口的矩形（逻辑像素）。
+pub fn window_rect(position: Point, size: f32) -> Rectangle {
+    Rectangle::new(position, Size::new(size, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use iced::{Point, Rectangle, Size};
+
+    use super::{Avoider, window_rect};
+
+    fn screen() -> Rectangle {
+        Rectangle::new(Point::ORIGIN, Size::new(1920.0, 1040.0))
+    }
+
+    #[test]
+    fn slides_to_the_nearest_free_corner_and_returns() {
+        let mut avoider = Avoider::default();
+        let ball = window_rect(Point::new(1700.0, 100.0), 120.0);
+        let start = Instant::now();
+        let window = |x: f32| Rectangle::new(Point::new(x, 50.0), Size::new(600.0, 400.0));
+
+        // 窗口静止或拖动但未经过悬浮球时不避让
+        assert_eq!(
+            avoider.update(Some(window(100.0)), ball, screen(), start),
+            None
+        );
+        assert_eq!(
+            avoider.update(Some(window(300.0)), ball, screen(), start),
+            None
+        );
+
+        // 拖到悬浮球下方：右上角被挡住，移到右下角
+        let target = avoider
+            .update(Some(window(1400.0)), ball, screen(), start)
+            .unwrap();
+        assert_eq!(
+            target,
+            Point::new(1920.0 - 120.0 - 16.0, 1040.0 - 120.0 - 16.0)
+        );
+        assert!(avoider.is_avoiding());
+
+        // 停下后等待一秒再回到原位
+        let moved = window_rect(target, 120.0);
+        let stopped = start + Duration::from_millis(200);
+        assert_eq!(
+            avoider.update(Some(window(1400.0)), moved, screen(), stopped),
+            None
+        );
+        assert_eq!(
+            avoider.update(
+                Some(window(1400.0)),
+                moved,
+                screen(),
+                stopped + Duration::from_secs(1)
+            ),
+            Some(Point::new(1700.0, 100.0))
+        );
+        assert!(!avoider.is_avoiding());
+    }
+
+    #[test]
+    fn ignores_resizing_windows() {
+        let mut avoider = Avoider::default();
+        let ball = window_rect(Point::new(100.0, 100.0), 120.0);
+        let now = Instant::now();
+        let small = Rectangle::new(Point::ORIGIN, Size::new(300.0, 300.0));
+        let large = Rectangle::new(Point::ORIGIN, Size::new(400.0, 400.0));
+        assert_eq!(avoider.update(Some(small), ball, screen(), now), None);
+        assert_eq!(avoider.update(Some(large), ball, screen(), now), None);
+    }
+
+    #[test]
+    fn ignores_the_ball_itself() {
+        let mut avoider = Avoider::default();
+        let now = Instant::now();
+        let ball = window_rect(Point::new(100.0, 100.0), 120.0);
+        let moved = window_rect(Point::new(110.0, 100.0), 120.0);
+        assert_eq!(avoider.update(Some(ball), ball, screen(), now), None);
+        assert_eq!(avoider.update(Some(moved), moved, screen(), now), None);
+    }
+}
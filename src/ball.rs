@@ -1,14 +1,44 @@
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
+
 use iced::widget::canvas::{self, Cache, Canvas, Frame, Geometry, Path, Program, Stroke};
-use iced::{Color, Element, Font, Point, Rectangle, Renderer, Size, Theme, mouse};
+use iced::widget::text::Shaping;
+use iced::{Color, Element, Font, Point, Rectangle, Renderer, Size, Theme, keyboard, mouse};
+
+use crate::api::FetchHealth;
+use crate::qr::QrMatrix;
+use crate::render_stats::{CacheLayer, RenderStats};
+use crate::skin::BallTheme;
+use crate::text_fit::{self, TextMetrics};
 
 const FONT_CN: Font = Font::with_name("Microsoft YaHei");
 const FONT_ICON: Font = Font::with_name("Segoe UI Symbol");
 
+/// 标题字号与标题行可用宽度（相对半径），两侧留出边距避免贴边。
+const TITLE_SIZE: f32 = 0.26;
+const TITLE_WIDTH: f32 = 1.6;
+/// 长标题滚动速度（半径/秒）。
+const MARQUEE_SPEED: f32 = 0.35;
+/// 光晕在球外每侧占用的宽度（相对直径），开启后窗口按此放大。
+const HALO_MARGIN: f32 = 0.16;
+/// 画布没有径向渐变，用若干同心圆环逐圈变淡来近似。
+const HALO_RINGS: usize = 14;
+
 #[derive(Debug, Clone)]
 pub enum BallEvent {
     StartDrag,
+    /// 手动拖动中光标移动或松开，位置由应用读取全局光标。
+    DragMove,
+    EndDrag,
     ToggleSettings,
     RefreshNow,
+    CopyValue,
+    HideQr,
+    BannerPressed,
+    /// 中键打开或关闭订阅总览。
+    ToggleGrid,
+    /// 点击底部的订阅圆点，直接切换到对应订阅。
+    SelectSubscription(usize),
     Scroll(i32),
     StartResize(Point),
     ResizeMove(Point),
@@ -20,6 +50,12 @@ pub enum BallStatus {
     Idle,
     Fetching,
     Error,
+    Anomaly,
+    ReAuth,
+    /// 超出自设的每日用量上限。
+    OverCap,
+    /// 消耗速度远超平时。
+    Spike,
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +64,18 @@ pub struct BallDisplay {
     pub value: String,
     pub ratio: f32,
     pub status: BallStatus,
+    pub flash: Option<String>,
+    pub hint: Option<String>,
+    /// 较昨天同一时刻的用量变化与是否用得更多，显示时替代提示文字。
+    pub delta: Option<(String, bool)>,
+    /// 可点击的提示条（如凭证失效），显示时替代数值。
+    pub banner: Option<String>,
+    /// 订阅数量与当前选中的下标，多于一个订阅时在底部绘制切换圆点。
+    pub dots: (usize, Option<usize>),
+    /// 数值为手动校正的值，在数值右侧绘制铅笔标记。
+    pub corrected: bool,
+    pub dimmed: bool,
+    pub health: Option<FetchHealth>,
 }
 
 impl Default for BallDisplay {
@@ -37,56 +85,237 @@ impl Default for BallDisplay {
             value: "--".to_string(),
             ratio: 0.0,
             status: BallStatus::Idle,
+            flash: None,
+            hint: None,
+            delta: None,
+            banner: None,
+            dots: (0, None),
+            corrected: false,
+            dimmed: false,
+            health: None,
         }
     }
 }
 
 pub struct FloatingBall {
     base_cache: Cache,
-    overlay_cache: Cache,
+    /// 边框、齿轮、健康点等随状态变化的装饰层。
+    chrome_cache: Cache,
+    /// 标题、数值与提示文字，每次刷新通常只需重绘这一层。
+    text_cache: Cache,
+    /// 球外的光晕，颜色随边框变化。
+    halo_cache: Cache,
+    halo: bool,
     display: BallDisplay,
+    /// 标题的估算宽度与方向，标题变化时重新计算。
+    title_metrics: TextMetrics,
+    /// 放不下的标题循环滚动，关闭时中间省略。
+    marquee: bool,
+    /// 滚动动画的时间（秒）。
+    marquee_time: f32,
+    qr: Option<QrMatrix>,
+    theme: BallTheme,
     wave_phase: f32,
+    debug_overlay: bool,
+    render_stats: RefCell<RenderStats>,
+    /// 设置页中的预览球只负责展示，不响应鼠标。
+    interactive: bool,
+    /// 拖动由应用移动窗口，而不是交给系统的拖动循环。
+    manual_drag: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct BallState {
     resizing: bool,
+    dragging: bool,
+    modifiers: keyboard::Modifiers,
 }
 
 impl FloatingBall {
     pub fn new(display: BallDisplay) -> Self {
         Self {
             base_cache: Cache::new(),
-            overlay_cache: Cache::new(),
+            chrome_cache: Cache::new(),
+            text_cache: Cache::new(),
+            halo_cache: Cache::new(),
+            halo: false,
+            title_metrics: TextMetrics::of(&display.title),
+            marquee: false,
+            marquee_time: 0.0,
             display,
+            qr: None,
+            theme: BallTheme::default(),
             wave_phase: 0.0,
+            debug_overlay: false,
+            render_stats: RefCell::new(RenderStats::default()),
+            interactive: true,
+            manual_drag: false,
+        }
+    }
+
+    pub fn preview(display: BallDisplay) -> Self {
+        Self {
+            interactive: false,
+            ..Self::new(display)
         }
     }
 
     pub fn set_display(&mut self, display: BallDisplay) {
-        let overlay_changed = self.display.title != display.title
-            || self.display.value != display.value
+        let chrome_changed = self.display.dimmed != display.dimmed
+            || self.display.health != display.health
             || std::mem::discriminant(&self.display.status)
                 != std::mem::discriminant(&display.status);
-
-        if overlay_changed {
-            self.overlay_cache.clear();
+        let text_changed = self.display.title != display.title
+            || self.display.value != display.value
+            || self.display.flash != display.flash
+            || self.display.hint != display.hint
+            || self.display.delta != display.delta
+            || self.display.banner != display.banner
+            || self.display.dots != display.dots
+            || self.display.corrected != display.corrected;
+
+        if chrome_changed {
+            self.chrome_cache.clear();
+            self.halo_cache.clear();
+        }
+        if text_changed {
+            self.text_cache.clear();
+        }
+        if self.display.title != display.title {
+            self.title_metrics = TextMetrics::of(&display.title);
         }
         self.display = display;
     }
 
+    pub fn set_qr(&mut self, qr: Option<QrMatrix>) {
+        if self.qr != qr {
+            self.chrome_cache.clear();
+            self.text_cache.clear();
+        }
+        self.qr = qr;
+    }
+
+    pub fn set_theme(&mut self, theme: BallTheme) {
+        if self.theme != theme {
+            self.base_cache.clear();
+            self.chrome_cache.clear();
+            self.text_cache.clear();
+            self.halo_cache.clear();
+        }
+        self.theme = theme;
+    }
+
     pub fn set_wave_phase(&mut self, phase: f32) {
         self.wave_phase = phase;
     }
 
+    /// 水位介于空与满之间时才有波浪需要逐帧重绘；滚动的标题同样需要。
+    pub fn is_animated(&self) -> bool {
+        (self.display.ratio > 0.0 && self.display.ratio < 1.0) || self.scrolls_title()
+    }
+
+    pub fn set_marquee(&mut self, enabled: bool) {
+        if self.marquee != enabled {
+            self.text_cache.clear();
+        }
+        self.marquee = enabled;
+    }
+
+    pub fn set_marquee_time(&mut self, seconds: f32) {
+        self.marquee_time = seconds;
+    }
+
+    /// 字号与可用宽度都随半径缩放，是否放得下与球的大小无关。
+    fn scrolls_title(&self) -> bool {
+        self.marquee && self.qr.is_none() && self.title_metrics.width > TITLE_WIDTH / TITLE_SIZE
+    }
+
+    pub fn set_halo(&mut self, enabled: bool) {
+        self.halo = enabled;
+    }
+
+    /// 窗口边长相对球体直径的倍数，开启光晕时四周留出光晕的位置。
+    pub fn halo_scale(&self) -> f32 {
+        if self.halo {
+            1.0 + 2.0 * HALO_MARGIN
+        } else {
+            1.0
+        }
+    }
+
+    pub fn set_manual_drag(&mut self, enabled: bool) {
+        self.manual_drag = enabled;
+    }
+
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        if self.debug_overlay != enabled {
+            self.render_stats.replace(RenderStats::default());
+        }
+        self.debug_overlay = enabled;
+    }
+
+    /// 通过缓存绘制一层，调试层开启时记录是否命中。
+    fn draw_cached(
+        &self,
+        cache: &Cache,
+        layer: CacheLayer,
+        renderer: &Renderer,
+        size: Size,
+        draw_fn: impl FnOnce(&mut Frame),
+    ) -> Geometry {
+        let missed = Cell::new(false);
+        let geometry = cache.draw(renderer, size, |frame| {
+            missed.set(true);
+            draw_fn(frame);
+        });
+        if self.debug_overlay {
+            self.render_stats
+                .borrow_mut()
+                .record_cache(layer, missed.get());
+        }
+        geometry
+    }
+
     pub fn view<'a, Message: 'a>(&'a self, size: f32) -> Element<'a, Message>
     where
         Message: From<BallEvent>,
     {
-        Canvas::new(self)
+        let ball = Canvas::new(self)
             .width(iced::Length::Fixed(size))
-            .height(iced::Length::Fixed(size))
-            .into()
+            .height(iced::Length::Fixed(size));
+        if !self.halo {
+            return ball.into();
+        }
+
+        // 光晕层只绘制不处理事件，球体居中叠在上面，点在光晕上不会触发任何操作
+        let outer = iced::Length::Fixed(size * self.halo_scale());
+        iced::widget::stack![
+            Canvas::new(Halo(self)).width(outer).height(outer),
+            iced::widget::container(ball).center(outer),
+        ]
+        .into()
+    }
+}
+
+/// 悬浮球背后的光晕层，与球体共用显示状态与皮肤。
+struct Halo<'a>(&'a FloatingBall);
+
+impl<Message> Program<Message> for Halo<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let ball = self.0;
+        let halo = ball.halo_cache.draw(renderer, bounds.size(), |frame| {
+            draw_halo(frame, bounds.size(), &ball.display, &ball.theme);
+        });
+        vec![halo]
     }
 }
 
@@ -104,24 +333,90 @@ where
         bounds: Rectangle,
         _cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
-        let base = self.base_cache.draw(renderer, bounds.size(), |frame| {
-            draw_base(frame, bounds.size())
-        });
+        let started = Instant::now();
+        if self.debug_overlay {
+            self.render_stats.borrow_mut().begin_frame(started);
+        }
+
+        let base = self.draw_cached(
+            &self.base_cache,
+            CacheLayer::Base,
+            renderer,
+            bounds.size(),
+            |frame| draw_base(frame, bounds.size(), &self.theme),
+        );
 
         let mut water_frame = Frame::new(renderer, bounds.size());
         draw_water(
             &mut water_frame,
             bounds.size(),
             &self.display,
+            &self.theme,
             self.wave_phase,
         );
         let water = water_frame.into_geometry();
 
-        let overlay = self.overlay_cache.draw(renderer, bounds.size(), |frame| {
-            draw_overlay(frame, bounds.size(), &self.display);
-        });
+        let chrome = self.draw_cached(
+            &self.chrome_cache,
+            CacheLayer::Chrome,
+            renderer,
+            bounds.size(),
+            |frame| {
+                draw_chrome(
+                    frame,
+                    bounds.size(),
+                    &self.display,
+                    &self.theme,
+                    self.qr.as_ref(),
+                );
+            },
+        );
+
+        let mut layers = vec![base, water, chrome];
+        // 水面与调试层本身每帧都重新生成
+        let mut rebuilt = 2;
+        // 二维码覆盖整个球面，不绘制文字
+        if self.qr.is_none() {
+            let (center, radius) = circle_layout(bounds.size());
+            let scrolls = self.scrolls_title();
+            layers.push(self.draw_cached(
+                &self.text_cache,
+                CacheLayer::Text,
+                renderer,
+                bounds.size(),
+                |frame| {
+                    if !scrolls {
+                        draw_title(frame, center, radius, &self.display.title, None);
+                    }
+                    draw_text(frame, center, radius, &self.display, &self.theme);
+                },
+            ));
+
+            // 滚动的标题逐帧重绘，其余文字仍走缓存
+            if scrolls {
+                let mut title_frame = Frame::new(renderer, bounds.size());
+                draw_title(
+                    &mut title_frame,
+                    center,
+                    radius,
+                    &self.display.title,
+                    Some((self.title_metrics, self.marquee_time)),
+                );
+                layers.push(title_frame.into_geometry());
+                rebuilt += 1;
+            }
+        }
+
+        if self.debug_overlay {
+            let mut stats = self.render_stats.borrow_mut();
+            stats.end_frame(started.elapsed(), layers.len() + 1, rebuilt);
 
-        vec![base, water, overlay]
+            let mut debug_frame = Frame::new(renderer, bounds.size());
+            draw_debug_overlay(&mut debug_frame, bounds.size(), &stats.lines());
+            layers.push(debug_frame.into_geometry());
+        }
+
+        layers
     }
 
     fn update(
@@ -131,6 +426,10 @@ where
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> (canvas::event::Status, Option<Message>) {
+        if !self.interactive {
+            return (canvas::event::Status::Ignored, None);
+        }
+
         let (center, radius) = circle_layout(bounds.size());
         let gear_hit = |position: Point| {
             let (gear_center, gear_radius) = gear_layout(center, radius);
@@ -151,6 +450,14 @@ where
                     return (canvas::event::Status::Ignored, None);
                 }
 
+                // 二维码显示时齿轮/手柄被隐藏，左键点击直接关闭二维码
+                if self.qr.is_some() {
+                    return (
+                        canvas::event::Status::Captured,
+                        Some(Message::from(BallEvent::HideQr)),
+                    );
+                }
+
                 if gear_hit(position) {
                     return (
                         canvas::event::Status::Captured,
@@ -158,6 +465,14 @@ where
                     );
                 }
 
+                if self.display.banner.is_some() && banner_layout(center, radius).contains(position)
+                {
+                    return (
+                        canvas::event::Status::Captured,
+                        Some(Message::from(BallEvent::BannerPressed)),
+                    );
+                }
+
                 if resize_hit(position) {
                     state.resizing = true;
 
@@ -171,11 +486,33 @@ where
                     );
                 }
 
+                let (dot_count, _) = self.display.dots;
+                if let Some(index) = dot_hit(center, radius, dot_count, position) {
+                    return (
+                        canvas::event::Status::Captured,
+                        Some(Message::from(BallEvent::SelectSubscription(index))),
+                    );
+                }
+
+                state.dragging = self.manual_drag;
                 (
                     canvas::event::Status::Captured,
                     Some(Message::from(BallEvent::StartDrag)),
                 )
             }
+            canvas::Event::Mouse(iced::mouse::Event::ButtonReleased(iced::mouse::Button::Left))
+                if state.dragging =>
+            {
+                state.dragging = false;
+                (
+                    canvas::event::Status::Captured,
+                    Some(Message::from(BallEvent::EndDrag)),
+                )
+            }
+            canvas::Event::Mouse(iced::mouse::Event::CursorMoved { .. }) if state.dragging => (
+                canvas::event::Status::Captured,
+                Some(Message::from(BallEvent::DragMove)),
+            ),
             canvas::Event::Mouse(iced::mouse::Event::ButtonReleased(iced::mouse::Button::Left))
                 if state.resizing =>
             {
@@ -209,11 +546,24 @@ where
                     return (canvas::event::Status::Ignored, None);
                 }
 
-                (
-                    canvas::event::Status::Captured,
-                    Some(Message::from(BallEvent::RefreshNow)),
-                )
+                // Ctrl/Cmd + 右键复制余额，普通右键刷新
+                let event = if state.modifiers.command() {
+                    BallEvent::CopyValue
+                } else {
+                    BallEvent::RefreshNow
+                };
+
+                (canvas::event::Status::Captured, Some(Message::from(event)))
             }
+            canvas::Event::Mouse(iced::mouse::Event::ButtonPressed(
+                iced::mouse::Button::Middle,
+            )) => match cursor.position_in(bounds) {
+                Some(position) if distance(position, center) <= radius => (
+                    canvas::event::Status::Captured,
+                    Some(Message::from(BallEvent::ToggleGrid)),
+                ),
+                _ => (canvas::event::Status::Ignored, None),
+            },
             canvas::Event::Mouse(iced::mouse::Event::WheelScrolled { delta }) => {
                 let Some(position) = cursor.position_in(bounds) else {
                     return (canvas::event::Status::Ignored, None);
@@ -239,6 +589,10 @@ where
                     Some(Message::from(BallEvent::Scroll(steps))),
                 )
             }
+            canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = modifiers;
+                (canvas::event::Status::Ignored, None)
+            }
             _ => (canvas::event::Status::Ignored, None),
         }
     }
@@ -249,9 +603,15 @@ where
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> mouse::Interaction {
+        if !self.interactive {
+            return mouse::Interaction::default();
+        }
         if state.resizing {
             return mouse::Interaction::ResizingDiagonallyDown;
         }
+        if state.dragging {
+            return mouse::Interaction::Grabbing;
+        }
 
         let Some(position) = cursor.position_in(bounds) else {
             return mouse::Interaction::None;
@@ -271,37 +631,55 @@ where
     }
 }
 
-fn draw_base(frame: &mut Frame, size: Size) {
+fn draw_base(frame: &mut Frame, size: Size, theme: &BallTheme) {
     let (center, radius) = circle_layout(size);
     let circle = Path::circle(center, radius);
 
-    // Matrix/极客风 - 深蓝黑渐变背景
+    // 背景由皮肤色向右下逐渐压暗（默认为深蓝到深黑）
+    let base = theme.background.0;
     let background = canvas::gradient::Linear::new(
         Point::new(center.x - radius, center.y - radius),
         Point::new(center.x + radius, center.y + radius),
     )
-    .add_stop(0.0, Color::from_rgba8(12, 25, 45, 245.0 / 255.0)) // 深蓝
-    .add_stop(0.5, Color::from_rgba8(8, 18, 32, 250.0 / 255.0)) // 中间
-    .add_stop(1.0, Color::from_rgba8(5, 12, 22, 255.0 / 255.0)); // 深黑
+    .add_stop(0.0, base)
+    .add_stop(0.5, darken(base, 0.7))
+    .add_stop(
+        1.0,
+        Color {
+            a: 1.0,
+            ..darken(base, 0.45)
+        },
+    );
 
     frame.fill(&circle, background);
 }
 
-fn draw_water(frame: &mut Frame, size: Size, display: &BallDisplay, phase: f32) {
+fn draw_water(frame: &mut Frame, size: Size, display: &BallDisplay, theme: &BallTheme, phase: f32) {
     let (center, radius) = circle_layout(size);
     let fill_ratio = display.ratio.clamp(0.0, 1.0);
     if fill_ratio <= 0.0 {
         return;
     }
 
-    // Matrix/极客风 - 蓝绿色渐变水面
+    let over_cap = matches!(display.status, BallStatus::OverCap);
     let water_gradient = canvas::gradient::Linear::new(
         Point::new(center.x, center.y - radius),
         Point::new(center.x, center.y + radius),
-    )
-    .add_stop(0.0, Color::from_rgba8(0, 255, 200, 200.0 / 255.0)) // #00ffc8 青绿色
-    .add_stop(0.5, Color::from_rgba8(0, 180, 255, 210.0 / 255.0)) // 中间过渡
-    .add_stop(1.0, Color::from_rgba8(0, 136, 255, 220.0 / 255.0)); // #0088ff 蓝色
+    );
+    let water_gradient = if over_cap {
+        // 已超额 - 灰色水面
+        water_gradient
+            .add_stop(0.0, Color::from_rgba8(150, 155, 160, 200.0 / 255.0))
+            .add_stop(1.0, Color::from_rgba8(80, 85, 92, 220.0 / 255.0))
+    } else {
+        // 皮肤水面渐变（默认为青绿到蓝色），可选的中间色位于正中
+        let water_gradient = water_gradient.add_stop(0.0, theme.water_top.0);
+        match theme.water_mid {
+            Some(mid) => water_gradient.add_stop(0.5, mid.0),
+            None => water_gradient,
+        }
+        .add_stop(1.0, theme.water_bottom.0)
+    };
 
     if fill_ratio >= 1.0 {
         frame.fill(&Path::circle(center, radius), water_gradient);
@@ -317,12 +695,17 @@ fn draw_water(frame: &mut Frame, size: Size, display: &BallDisplay, phase: f32)
     frame.fill(&water_path, Color::from_rgba8(0, 30, 60, 25.0 / 255.0));
 
     if let Some(wave_line) = wave_surface_path(center, radius, fill_ratio, phase) {
-        // 波浪高光 - 霓虹绿
+        // 波浪高光 - 霓虹绿（超额时为浅灰）
+        let highlight = if over_cap {
+            Color::from_rgba8(200, 200, 205, 140.0 / 255.0)
+        } else {
+            Color::from_rgba8(0, 255, 180, 140.0 / 255.0)
+        };
         frame.stroke(
             &wave_line,
             Stroke::default()
                 .with_width((radius * 0.035).max(1.6))
-                .with_color(Color::from_rgba8(0, 255, 180, 140.0 / 255.0)),
+                .with_color(highlight),
         );
         // 波浪阴影线
         frame.stroke(
@@ -334,16 +717,21 @@ fn draw_water(frame: &mut Frame, size: Size, display: &BallDisplay, phase: f32)
     }
 }
 
-fn draw_overlay(frame: &mut Frame, size: Size, display: &BallDisplay) {
+fn draw_chrome(
+    frame: &mut Frame,
+    size: Size,
+    display: &BallDisplay,
+    theme: &BallTheme,
+    qr: Option<&QrMatrix>,
+) {
     let (center, radius) = circle_layout(size);
     let circle = Path::circle(center, radius);
+    let outline_color = outline_color(&display.status, theme);
 
-    // Matrix/极客风边框颜色
-    let outline_color = match &display.status {
-        BallStatus::Error => Color::from_rgb8(255, 60, 90), // 霓虹红
-        BallStatus::Fetching => Color::from_rgb8(0, 200, 255), // 霓虹青
-        BallStatus::Idle => Color::from_rgba8(0, 255, 136, 220.0 / 255.0), // 霓虹绿
-    };
+    // 静默时段整体压暗（覆盖在水面之上，文字之下）
+    if display.dimmed {
+        frame.fill(&circle, Color::from_rgba8(0, 0, 0, 110.0 / 255.0));
+    }
 
     // 发光边框
     frame.stroke(
@@ -351,78 +739,262 @@ fn draw_overlay(frame: &mut Frame, size: Size, display: &BallDisplay) {
         Stroke::default().with_width(2.5).with_color(outline_color),
     );
 
-    draw_text(frame, center, radius, display);
+    if let Some(qr) = qr {
+        draw_qr(frame, center, radius, qr);
+        return;
+    }
+
     draw_gear(frame, center, radius);
+    if let Some(health) = display.health {
+        draw_health_dot(frame, center, radius, health);
+    }
     draw_resize_handle(frame, center, radius);
 }
 
-fn draw_text(frame: &mut Frame, center: Point, radius: f32, display: &BallDisplay) {
+// Matrix/极客风边框颜色
+fn outline_color(status: &BallStatus, theme: &BallTheme) -> Color {
+    match status {
+        BallStatus::Error => Color::from_rgb8(255, 60, 90), // 霓虹红
+        BallStatus::Fetching => Color::from_rgb8(0, 200, 255), // 霓虹青
+        BallStatus::Anomaly => Color::from_rgb8(255, 200, 60), // 琥珀色
+        BallStatus::ReAuth => Color::from_rgb8(255, 90, 220), // 霓虹品红
+        BallStatus::OverCap => Color::from_rgb8(230, 30, 30), // 警示红
+        BallStatus::Spike => Color::from_rgb8(170, 80, 255), // 霓虹紫
+        BallStatus::Idle => theme.outline.0,                // 皮肤边框色，默认霓虹绿
+    }
+}
+
+/// 从边框向外逐圈变淡的光晕，颜色与边框一致；静默时段同样压暗。
+fn draw_halo(frame: &mut Frame, size: Size, display: &BallDisplay, theme: &BallTheme) {
+    let center = Point::new(size.width / 2.0, size.height / 2.0);
+    let outer = size.width.min(size.height) / 2.0;
+    let inner = outer / (1.0 + 2.0 * HALO_MARGIN) * 0.99;
+    let ring_width = (outer - inner) / HALO_RINGS as f32;
+    let color = outline_color(&display.status, theme);
+    let strength = if display.dimmed { 0.25 } else { 0.5 };
+
+    for ring in 0..HALO_RINGS {
+        let t = (ring as f32 + 0.5) / HALO_RINGS as f32;
+        // 按平方衰减，接近边框处最亮，外缘完全透明
+        let alpha = color.a * strength * (1.0 - t).powi(2);
+        frame.stroke(
+            &Path::circle(center, inner + ring_width * (ring as f32 + 0.5)),
+            // 相邻圆环略微重叠，避免出现缝隙
+            Stroke::default()
+                .with_width(ring_width + 0.5)
+                .with_color(Color { a: alpha, ..color }),
+        );
+    }
+}
+
+fn draw_qr(frame: &mut Frame, center: Point, radius: f32, qr: &QrMatrix) {
+    // 内接正方形（留出 quiet zone），白底黑码保证手机可识别
+    let side = radius * std::f32::consts::SQRT_2 * 0.92;
+    let quiet_zone = 2;
+    let cells = qr.width() + quiet_zone * 2;
+    let cell = side / cells as f32;
+    let origin = Point::new(center.x - side / 2.0, center.y - side / 2.0);
+
+    frame.fill_rectangle(origin, Size::new(side, side), Color::WHITE);
+
+    let dark = Color::from_rgb8(5, 12, 22);
+    for y in 0..qr.width() {
+        for x in 0..qr.width() {
+            if !qr.is_dark(x, y) {
+                continue;
+            }
+
+            let top_left = Point::new(
+                origin.x + (x + quiet_zone) as f32 * cell,
+                origin.y + (y + quiet_zone) as f32 * cell,
+            );
+            // 略微放大单元格避免相邻模块之间出现抗锯齿缝隙
+            frame.fill_rectangle(top_left, Size::new(cell + 0.3, cell + 0.3), dark);
+        }
+    }
+}
+
+fn draw_text(
+    frame: &mut Frame,
+    center: Point,
+    radius: f32,
+    display: &BallDisplay,
+    theme: &BallTheme,
+) {
     use iced::widget::canvas::Text;
 
-    // 科技感文字颜色 - 淡青绿色
-    let title_color = Color::from_rgba8(200, 255, 240, 230.0 / 255.0);
-    let value_color = Color::from_rgba8(0, 255, 200, 245.0 / 255.0); // 霓虹绿
+    let value_color = theme.text.0; // 默认霓虹绿
     let small_color = Color::from_rgba8(100, 200, 180, 180.0 / 255.0);
 
     let shadow = Color::from_rgba8(0, 40, 60, 180.0 / 255.0);
     let shadow_offset = (radius * 0.03).max(1.0);
 
-    let title_position = Point::new(center.x, center.y - radius * 0.18);
-    frame.fill_text(Text {
-        content: display.title.clone(),
-        position: Point::new(
-            title_position.x + shadow_offset,
-            title_position.y + shadow_offset,
-        ),
-        color: shadow,
-        size: iced::Pixels((radius * 0.26).max(10.0)),
-        font: FONT_CN,
-        horizontal_alignment: iced::alignment::Horizontal::Center,
-        vertical_alignment: iced::alignment::Vertical::Center,
-        ..Text::default()
-    });
+    if let Some(banner) = &display.banner {
+        draw_banner(frame, center, radius, banner);
+    } else {
+        let value_position = Point::new(center.x, center.y + radius * 0.10);
+        frame.fill_text(Text {
+            content: display.value.clone(),
+            position: Point::new(
+                value_position.x + shadow_offset,
+                value_position.y + shadow_offset,
+            ),
+            color: shadow,
+            size: iced::Pixels((radius * 0.34).max(12.0)),
+            font: FONT_CN,
+            horizontal_alignment: iced::alignment::Horizontal::Center,
+            vertical_alignment: iced::alignment::Vertical::Center,
+            ..Text::default()
+        });
+        frame.fill_text(Text {
+            content: display.value.clone(),
+            position: value_position,
+            color: value_color,
+            size: iced::Pixels((radius * 0.34).max(12.0)),
+            font: FONT_CN,
+            horizontal_alignment: iced::alignment::Horizontal::Center,
+            vertical_alignment: iced::alignment::Vertical::Center,
+            ..Text::default()
+        });
+
+        if display.corrected {
+            let at = Point::new(center.x + radius * 0.66, value_position.y - radius * 0.08);
+            draw_pencil(frame, at, (radius * 0.12).max(5.0), small_color);
+        }
+    }
+
+    let (hint, hint_color) = match (&display.flash, &display.delta, &display.hint) {
+        (Some(flash), _, _) => (flash.as_str(), value_color),
+        (None, Some((delta, true)), _) => (delta.as_str(), Color::from_rgb8(255, 140, 90)),
+        (None, Some((delta, false)), _) => (delta.as_str(), Color::from_rgb8(0, 255, 136)),
+        (None, None, Some(hint)) => (hint.as_str(), small_color),
+        (None, None, None) => ("滚轮切换 · 右键刷新", small_color),
+    };
+    draw_dots(frame, center, radius, display.dots, value_color);
     frame.fill_text(Text {
-        content: display.title.clone(),
-        position: title_position,
-        color: title_color,
-        size: iced::Pixels((radius * 0.26).max(10.0)),
+        content: hint.to_string(),
+        position: Point::new(center.x, center.y + radius * 0.42),
+        color: hint_color,
+        size: iced::Pixels((radius * 0.18).max(9.0)),
         font: FONT_CN,
         horizontal_alignment: iced::alignment::Horizontal::Center,
         vertical_alignment: iced::alignment::Vertical::Center,
         ..Text::default()
     });
+}
 
-    let value_position = Point::new(center.x, center.y + radius * 0.10);
-    frame.fill_text(Text {
-        content: display.value.clone(),
-        position: Point::new(
-            value_position.x + shadow_offset,
-            value_position.y + shadow_offset,
-        ),
-        color: shadow,
-        size: iced::Pixels((radius * 0.34).max(12.0)),
-        font: FONT_CN,
-        horizontal_alignment: iced::alignment::Horizontal::Center,
-        vertical_alignment: iced::alignment::Vertical::Center,
-        ..Text::default()
+/// 标题：放得下时居中，放不下时中间省略；`scroll` 为标题度量与动画时间，
+/// 此时在标题行内循环滚动，从右到左的文字向右滚动。
+fn draw_title(
+    frame: &mut Frame,
+    center: Point,
+    radius: f32,
+    title: &str,
+    scroll: Option<(TextMetrics, f32)>,
+) {
+    use iced::alignment::Horizontal;
+    use iced::widget::canvas::Text;
+
+    // 科技感文字颜色 - 淡青绿色
+    let title_color = Color::from_rgba8(200, 255, 240, 230.0 / 255.0);
+    let shadow = Color::from_rgba8(0, 40, 60, 180.0 / 255.0);
+    let shadow_offset = (radius * 0.03).max(1.0);
+
+    let size = (radius * TITLE_SIZE).max(10.0);
+    let max_width = radius * TITLE_WIDTH;
+    let position = Point::new(center.x, center.y - radius * 0.18);
+    // 阿拉伯文等需要完整的字形排布
+    let shaping = if title.is_ascii() {
+        Shaping::Basic
+    } else {
+        Shaping::Advanced
+    };
+    let draw = |frame: &mut Frame, content: &str, at: Point, align: Horizontal| {
+        for (offset, color) in [(shadow_offset, shadow), (0.0, title_color)] {
+            frame.fill_text(Text {
+                content: content.to_string(),
+                position: Point::new(at.x + offset, at.y + offset),
+                color,
+                size: iced::Pixels(size),
+                font: FONT_CN,
+                horizontal_alignment: align,
+                vertical_alignment: iced::alignment::Vertical::Center,
+                shaping,
+                ..Text::default()
+            });
+        }
+    };
+
+    let Some((metrics, time)) = scroll else {
+        let fitted = text_fit::middle_ellipsis(title, max_width / size);
+        draw(frame, &fitted, position, Horizontal::Center);
+        return;
+    };
+
+    let width = metrics.width * size;
+    let period = width + size * 2.0;
+    let offset = (time * radius * MARQUEE_SPEED).rem_euclid(period);
+    let clip = Rectangle::new(
+        Point::new(center.x - max_width / 2.0, position.y - size),
+        Size::new(max_width, size * 2.0),
+    );
+    // 裁剪区域内使用以区域左上角为原点的坐标，首尾相接画两份
+    frame.with_clip(clip, |frame| {
+        let start = if metrics.rtl {
+            max_width - width + offset
+        } else {
+            -offset
+        };
+        let step = if metrics.rtl { -period } else { period };
+        for x in [start, start + step] {
+            draw(frame, title, Point::new(x, size), Horizontal::Left);
+        }
     });
-    frame.fill_text(Text {
-        content: display.value.clone(),
-        position: value_position,
-        color: value_color,
-        size: iced::Pixels((radius * 0.34).max(12.0)),
-        font: FONT_CN,
-        horizontal_alignment: iced::alignment::Horizontal::Center,
-        vertical_alignment: iced::alignment::Vertical::Center,
-        ..Text::default()
+}
+
+/// 手动校正标记：斜放的笔杆加笔尖，`at` 为中心。
+fn draw_pencil(frame: &mut Frame, at: Point, size: f32, color: Color) {
+    let half = size / 2.0;
+    let tip = Point::new(at.x - half, at.y + half);
+    let body = Path::line(
+        Point::new(tip.x + size * 0.3, tip.y - size * 0.3),
+        Point::new(at.x + half, at.y - half),
+    );
+    frame.stroke(
+        &body,
+        Stroke::default().with_width(size * 0.3).with_color(color),
+    );
+    let nib = Path::new(|builder| {
+        builder.move_to(tip);
+        builder.line_to(Point::new(tip.x + size * 0.1, tip.y - size * 0.4));
+        builder.line_to(Point::new(tip.x + size * 0.4, tip.y - size * 0.1));
+        builder.close();
     });
+    frame.fill(&nib, color);
+}
+
+fn draw_banner(frame: &mut Frame, center: Point, radius: f32, banner: &str) {
+    use iced::widget::canvas::Text;
+
+    let bounds = banner_layout(center, radius);
+    let pill = Path::rounded_rectangle(
+        bounds.position(),
+        bounds.size(),
+        (bounds.height / 2.0).into(),
+    );
 
-    let hint = "滚轮切换 · 右键刷新";
+    frame.fill(&pill, Color::from_rgba8(60, 10, 50, 220.0 / 255.0));
+    frame.stroke(
+        &pill,
+        Stroke::default()
+            .with_width(1.2)
+            .with_color(Color::from_rgb8(255, 90, 220)), // 与重新验证的边框同色
+    );
     frame.fill_text(Text {
-        content: hint.to_string(),
-        position: Point::new(center.x, center.y + radius * 0.42),
-        color: small_color,
-        size: iced::Pixels((radius * 0.18).max(9.0)),
+        content: banner.to_string(),
+        position: bounds.center(),
+        color: Color::from_rgb8(255, 220, 245),
+        size: iced::Pixels((radius * 0.16).max(9.0)),
         font: FONT_CN,
         horizontal_alignment: iced::alignment::Horizontal::Center,
         vertical_alignment: iced::alignment::Vertical::Center,
@@ -430,6 +1002,63 @@ fn draw_text(frame: &mut Frame, center: Point, radius: f32, display: &BallDispla
     });
 }
 
+fn draw_debug_overlay(frame: &mut Frame, size: Size, lines: &[String]) {
+    use iced::widget::canvas::Text;
+
+    let (center, radius) = circle_layout(size);
+    let font_size = (radius * 0.11).max(8.0);
+    let line_height = font_size * 1.3;
+    let panel = Size::new(
+        radius * 1.3,
+        line_height * lines.len() as f32 + font_size * 0.6,
+    );
+    let top_left = Point::new(center.x - panel.width / 2.0, center.y - radius * 0.72);
+
+    frame.fill_rectangle(top_left, panel, Color::from_rgba8(0, 0, 0, 170.0 / 255.0));
+    for (index, line) in lines.iter().enumerate() {
+        frame.fill_text(Text {
+            content: line.clone(),
+            position: Point::new(
+                center.x,
+                top_left.y + font_size * 0.3 + line_height * (index as f32 + 0.5),
+            ),
+            color: Color::from_rgb8(255, 230, 120),
+            size: iced::Pixels(font_size),
+            font: FONT_CN,
+            horizontal_alignment: iced::alignment::Horizontal::Center,
+            vertical_alignment: iced::alignment::Vertical::Center,
+            ..Text::default()
+        });
+    }
+}
+
+fn draw_dots(
+    frame: &mut Frame,
+    center: Point,
+    radius: f32,
+    (count, selected): (usize, Option<usize>),
+    color: Color,
+) {
+    let Some((positions, dot_radius)) = dot_layout(center, radius, count) else {
+        return;
+    };
+
+    for (index, position) in positions.into_iter().enumerate() {
+        let dot = Path::circle(position, dot_radius);
+        if selected == Some(index) {
+            frame.fill(&dot, color);
+        } else {
+            frame.fill(&dot, Color::from_rgba8(5, 20, 35, 160.0 / 255.0));
+            frame.stroke(
+                &dot,
+                Stroke::default()
+                    .with_width(1.0)
+                    .with_color(Color { a: 0.7, ..color }),
+            );
+        }
+    }
+}
+
 fn draw_gear(frame: &mut Frame, center: Point, radius: f32) {
     use iced::widget::canvas::Text;
 
@@ -457,6 +1086,77 @@ fn draw_gear(frame: &mut Frame, center: Point, radius: f32) {
     });
 }
 
+fn draw_health_dot(frame: &mut Frame, center: Point, radius: f32, health: FetchHealth) {
+    // 左上角的小圆点：绿 <500ms，黄 <2s，红 更慢或失败
+    let color = match health {
+        FetchHealth::Good => Color::from_rgb8(0, 255, 136),
+        FetchHealth::Slow => Color::from_rgb8(255, 200, 60),
+        FetchHealth::Bad => Color::from_rgb8(255, 60, 90),
+    };
+    let dot_center = Point::new(center.x - radius * 0.55, center.y - radius * 0.55);
+    let dot = Path::circle(dot_center, (radius * 0.06).max(3.0));
+
+    frame.fill(&dot, color);
+    frame.stroke(
+        &dot,
+        Stroke::default()
+            .with_width(1.0)
+            .with_color(Color::from_rgba8(5, 20, 35, 200.0 / 255.0)),
+    );
+}
+
+fn darken(color: Color, factor: f32) -> Color {
+    Color {
+        r: color.r * factor,
+        g: color.g * factor,
+        b: color.b * factor,
+        a: color.a,
+    }
+}
+
+/// 主题市场中的迷你悬浮球预览：背景、60% 水位与边框。
+struct ThemePreview {
+    theme: BallTheme,
+}
+
+pub fn theme_preview<'a, Message: 'a>(theme: BallTheme, size: f32) -> Element<'a, Message> {
+    Canvas::new(ThemePreview { theme })
+        .width(iced::Length::Fixed(size))
+        .height(iced::Length::Fixed(size))
+        .into()
+}
+
+impl<Message> Program<Message> for ThemePreview {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let size = bounds.size();
+        let display = BallDisplay {
+            ratio: 0.6,
+            ..BallDisplay::default()
+        };
+
+        let mut frame = Frame::new(renderer, size);
+        draw_base(&mut frame, size, &self.theme);
+        draw_water(&mut frame, size, &display, &self.theme, 0.0);
+        let (center, radius) = circle_layout(size);
+        frame.stroke(
+            &Path::circle(center, radius),
+            Stroke::default()
+                .with_width(2.0)
+                .with_color(self.theme.outline.0),
+        );
+        vec![frame.into_geometry()]
+    }
+}
+
 fn circle_layout(size: Size) -> (Point, f32) {
     // 使用接近 0.5 的比例，让圆填满窗口，避免缩放时露出边角
     let radius = (size.width.min(size.height) * 0.495).max(1.0);
@@ -470,6 +1170,49 @@ fn gear_layout(center: Point, radius: f32) -> (Point, f32) {
     (gear_center, gear_radius)
 }
 
+/// 提示条占据数值所在的一行。
+fn banner_layout(center: Point, radius: f32) -> Rectangle {
+    let size = Size::new(radius * 1.5, radius * 0.26);
+    Rectangle::new(
+        Point::new(
+            center.x - size.width / 2.0,
+            center.y + radius * 0.10 - size.height / 2.0,
+        ),
+        size,
+    )
+}
+
+/// 最多绘制的订阅圆点数，更多时只能用滚轮切换。
+const MAX_DOTS: usize = 10;
+
+/// 底部内缘一排圆点的圆心与半径，位于提示文字下方、调整手柄左侧。
+fn dot_layout(center: Point, radius: f32, count: usize) -> Option<(Vec<Point>, f32)> {
+    if !(2..=MAX_DOTS).contains(&count) {
+        return None;
+    }
+
+    let spacing = (radius * 0.14).min(radius * 0.6 / (count - 1) as f32);
+    let dot_radius = (spacing * 0.3).clamp(1.5, radius * 0.04);
+    let y = center.y + radius * 0.68;
+    let left = center.x - spacing * (count - 1) as f32 / 2.0;
+    let positions = (0..count)
+        .map(|index| Point::new(left + spacing * index as f32, y))
+        .collect();
+    Some((positions, dot_radius))
+}
+
+/// 命中区域比圆点本身大一些，便于在小尺寸的悬浮球上点中。
+fn dot_hit(center: Point, radius: f32, count: usize, position: Point) -> Option<usize> {
+    let (positions, dot_radius) = dot_layout(center, radius, count)?;
+    let spacing = positions
+        .get(1)
+        .map_or(dot_radius * 2.0, |second| second.x - positions[0].x);
+    let reach = (dot_radius * 2.0).max(spacing / 2.0);
+    positions
+        .iter()
+        .position(|dot| distance(position, *dot) <= reach)
+}
+
 fn resize_layout(center: Point, radius: f32) -> (Point, f32) {
     let handle_radius = radius * 0.22;
     let handle_center = Point::new(center.x + radius * 0.55, center.y + radius * 0.55);
@@ -0,0 +1,133 @@
+use std::time::{Duration, Instant};
+
+use iced::widget::canvas::Program;
+use iced::{Font, Pixels, Rectangle, Renderer, Size, Theme, mouse};
+
+use crate::about;
+use crate::ball::{BallDisplay, BallEvent, BallState, FloatingBall};
+
+/// 悬浮球大小的下限、默认值、常用值与上限。
+const SIZES: [f32; 4] = [80.0, 120.0, 160.0, 220.0];
+
+/// 一组帧的耗时统计。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl BenchStats {
+    /// 空样本返回 `None`。
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let total: Duration = samples.iter().sum();
+        let percentile = |p: usize| samples[(samples.len() - 1) * p / 100];
+        Some(Self {
+            mean: total / samples.len() as u32,
+            p50: percentile(50),
+            p95: percentile(95),
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+/// `--bench-render`：在不打开窗口的情况下逐帧生成悬浮球的画布几何，输出各尺寸的耗时。
+/// 「animated」只重绘水面（与波浪动画相同），「cold」每帧清空缓存、重绘所有图层。
+/// 只计几何生成，不含光栅化与上屏。
+pub fn run(frames: usize) -> String {
+    let renderer = Renderer::new(Font::DEFAULT, Pixels(16.0));
+    let display = BallDisplay {
+        title: "Bench".to_string(),
+        value: "42.0".to_string(),
+        ratio: 0.42,
+        dots: (3, Some(1)),
+        ..BallDisplay::default()
+    };
+
+    let mut lines = vec![
+        format!(
+            "rightcode-floatingball {} render bench ({}, {frames} frames per row)",
+            about::VERSION,
+            about::build_info()
+        ),
+        format!(
+            "{:>5}  {:<8}  {:>9}  {:>9}  {:>9}  {:>9}",
+            "size", "scenario", "mean", "p50", "p95", "max"
+        ),
+    ];
+
+    for size in SIZES {
+        let bounds = Rectangle::with_size(Size::new(size, size));
+        let mut ball = FloatingBall::new(display.clone());
+        let animated = sample(frames, |frame| {
+            ball.set_wave_phase(frame as f32 * 0.07);
+            draw(&ball, &renderer, bounds);
+        });
+        let cold = sample(frames, |_| {
+            draw(&FloatingBall::new(display.clone()), &renderer, bounds);
+        });
+
+        for (scenario, mut samples) in [("animated", animated), ("cold", cold)] {
+            if let Some(stats) = BenchStats::from_samples(&mut samples) {
+                lines.push(format!(
+                    "{size:>5}  {scenario:<8}  {:>9}  {:>9}  {:>9}  {:>9}",
+                    format_duration(stats.mean),
+                    format_duration(stats.p50),
+                    format_duration(stats.p95),
+                    format_duration(stats.max),
+                ));
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn sample(frames: usize, mut render: impl FnMut(usize)) -> Vec<Duration> {
+    (0..frames)
+        .map(|frame| {
+            let started = Instant::now();
+            render(frame);
+            started.elapsed()
+        })
+        .collect()
+}
+
+fn draw(ball: &FloatingBall, renderer: &Renderer, bounds: Rectangle) {
+    let geometry = <FloatingBall as Program<BallEvent>>::draw(
+        ball,
+        &BallState::default(),
+        renderer,
+        &Theme::Dark,
+        bounds,
+        mouse::Cursor::Unavailable,
+    );
+    std::hint::black_box(geometry);
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} µs", duration.as_secs_f64() * 1_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::BenchStats;
+
+    #[test]
+    fn summarizes_samples() {
+        let mut samples: Vec<Duration> = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = BenchStats::from_samples(&mut samples).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(50_500));
+        assert_eq!(stats.p50, Duration::from_micros(50));
+        assert_eq!(stats.p95, Duration::from_micros(95));
+        assert_eq!(stats.max, Duration::from_micros(100));
+        assert!(BenchStats::from_samples(&mut []).is_none());
+    }
+}
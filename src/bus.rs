@@ -0,0 +1,75 @@
+use std::sync::{LazyLock, Mutex};
+
+use iced::futures::StreamExt;
+use iced::futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use iced::futures::stream::{self, BoxStream};
+
+use crate::platform::JumpTask;
+use crate::tray::TrayEvent;
+
+/// 托盘、窗口过程等其他线程发给应用的命令，经订阅转为 `Message::Command`。
+#[derive(Debug, Clone)]
+pub enum Command {
+    Tray(TrayEvent),
+    /// 分辨率、DPI 或工作区（任务栏）变化；其他平台由定时检查兜底。
+    #[cfg(windows)]
+    DisplayChanged,
+    /// 任务栏跳转列表中的任务（由另一个进程转交，或随启动参数传入）。
+    JumpTask(JumpTask),
+    /// 按下了全局快捷键（`HotKey::id`）。
+    Hotkey(u32),
+}
+
+struct Bus {
+    sender: UnboundedSender<Command>,
+    receiver: Mutex<Option<UnboundedReceiver<Command>>>,
+}
+
+static BUS: LazyLock<Bus> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::unbounded();
+    Bus {
+        sender,
+        receiver: Mutex::new(Some(receiver)),
+    }
+});
+
+/// 可在任意线程调用；应用已退出时直接丢弃。
+pub fn send(command: Command) {
+    let _ = BUS.sender.unbounded_send(command);
+}
+
+/// 供 `Subscription::run` 使用的命令流；接收端只能取走一次，之后返回空流。
+pub fn commands() -> BoxStream<'static, Command> {
+    let receiver = BUS
+        .receiver
+        .lock()
+        .ok()
+        .and_then(|mut receiver| receiver.take());
+    match receiver {
+        Some(receiver) => receiver.boxed(),
+        None => stream::empty().boxed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::futures::StreamExt;
+
+    use super::{Command, commands, send};
+
+    #[test]
+    fn delivers_commands_sent_before_subscribing() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        send(Command::Hotkey(7));
+
+        let mut stream = commands();
+        assert!(matches!(
+            runtime.block_on(stream.next()),
+            Some(Command::Hotkey(7))
+        ));
+        // 接收端只有一个
+        assert!(runtime.block_on(commands().next()).is_none());
+    }
+}
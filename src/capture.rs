@@ -0,0 +1,193 @@
+use std::path::Path;
+use std::time::Duration;
+
+use iced::advanced::graphics::Viewport;
+use iced::advanced::graphics::geometry::Renderer as _;
+use iced::widget::canvas::Program;
+use iced::{Color, Font, Pixels, Rectangle, Renderer, Size, Theme, mouse};
+
+use crate::ball::{BallEvent, BallState, FloatingBall};
+
+/// 可选的导出分辨率（像素，正方形）。
+pub const EXPORT_SIZES: [u32; 4] = [128, 256, 512, 1024];
+
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("empty screenshot")]
+    EmptyFrame,
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("png error: {0}")]
+    Png(#[from] png::EncodingError),
+    #[error("gif error: {0}")]
+    Gif(#[from] gif::EncodingError),
+}
+
+/// 按目标尺寸在内存中重新绘制悬浮球（不经过窗口截图，大尺寸也清晰），圆外像素透明。
+pub fn render_ball(ball: &FloatingBall, size: u32) -> Option<Vec<u8>> {
+    let mut pixmap = tiny_skia::Pixmap::new(size, size)?;
+    let mut clip_mask = tiny_skia::Mask::new(size, size)?;
+    let bounds = Rectangle::with_size(Size::new(size as f32, size as f32));
+
+    let mut renderer = Renderer::new(Font::default(), Pixels(16.0));
+    let layers = <FloatingBall as Program<BallEvent>>::draw(
+        ball,
+        &BallState::default(),
+        &renderer,
+        &Theme::Dark,
+        bounds,
+        mouse::Cursor::Unavailable,
+    );
+    for layer in layers {
+        renderer.draw_geometry(layer);
+    }
+    renderer.draw::<&str>(
+        &mut pixmap.as_mut(),
+        &mut clip_mask,
+        &Viewport::with_physical_size(Size::new(size, size), 1.0),
+        &[bounds],
+        Color::TRANSPARENT,
+        &[],
+    );
+
+    let mut frame = straight_rgba(pixmap.data());
+    mask_circle(&mut frame, size);
+    Some(frame)
+}
+
+/// 渲染结果为预乘 alpha 的 BGRA，转换为 PNG/GIF 使用的非预乘 RGBA。
+fn straight_rgba(bgra: &[u8]) -> Vec<u8> {
+    bgra.chunks_exact(4)
+        .flat_map(|pixel| {
+            let [b, g, r, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+            let unmultiply = |c: u8| match a {
+                0 => 0,
+                a => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+            };
+            [unmultiply(r), unmultiply(g), unmultiply(b), a]
+        })
+        .collect()
+}
+
+/// 与悬浮球的圆形布局一致，圆外完全透明，避免带出桌面背景。
+fn mask_circle(rgba: &mut [u8], size: u32) {
+    let center = size as f32 / 2.0;
+    let radius = size as f32 * 0.495;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f32 + 0.5 - center;
+            let dy = y as f32 + 0.5 - center;
+            if (dx * dx + dy * dy).sqrt() > radius {
+                let idx = ((y * size + x) * 4) as usize;
+                rgba[idx..idx + 4].fill(0);
+            }
+        }
+    }
+}
+
+fn encode_png(rgba: &[u8], size: u32) -> Result<Vec<u8>, CaptureError> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, size, size);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(rgba)?;
+    Ok(bytes)
+}
+
+pub fn save_png(frame: &[u8], size: u32, path: &Path) -> Result<(), CaptureError> {
+    if frame.len() != (size * size * 4) as usize {
+        return Err(CaptureError::EmptyFrame);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, encode_png(frame, size)?)?;
+    Ok(())
+}
+
+/// 将若干帧（均由 [`render_ball`] 绘制）编码为循环播放的 GIF，`delay` 为每帧间隔。
+fn encode_gif(frames: &[Vec<u8>], size: u32, delay: Duration) -> Result<Vec<u8>, CaptureError> {
+    let side = u16::try_from(size).map_err(|_| CaptureError::EmptyFrame)?;
+    if frames.is_empty() {
+        return Err(CaptureError::EmptyFrame);
+    }
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut bytes, side, side, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        // GIF 的帧间隔单位为 1/100 秒
+        let delay = (delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+        for frame in frames {
+            let mut pixels = frame.clone();
+            let mut frame = gif::Frame::from_rgba_speed(side, side, &mut pixels, 10);
+            frame.delay = delay;
+            // 透明背景需要逐帧清除，否则上一帧会残留
+            frame.dispose = gif::DisposalMethod::Background;
+            encoder.write_frame(&frame)?;
+        }
+    }
+    Ok(bytes)
+}
+
+pub fn save_gif(
+    frames: &[Vec<u8>],
+    size: u32,
+    delay: Duration,
+    path: &Path,
+) -> Result<(), CaptureError> {
+    let bytes = encode_gif(frames, size, delay)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{encode_gif, encode_png, mask_circle, render_ball, straight_rgba};
+    use crate::ball::{BallDisplay, FloatingBall};
+
+    #[test]
+    fn renders_the_ball_at_the_requested_size() {
+        let ball = FloatingBall::new(BallDisplay::default());
+        let frame = render_ball(&ball, 256).unwrap();
+        assert_eq!(frame.len(), 256 * 256 * 4);
+        assert_eq!(frame[3], 0);
+        let center = ((128 * 256 + 128) * 4) as usize;
+        assert!(frame[center + 3] > 0);
+    }
+
+    #[test]
+    fn masks_pixels_outside_the_ball() {
+        let mut frame = vec![255u8; 8 * 8 * 4];
+        mask_circle(&mut frame, 8);
+        // 角落在圆外，被设为透明；中心保持原色
+        assert_eq!(&frame[0..4], &[0, 0, 0, 0]);
+        let center = ((4 * 8 + 4) * 4) as usize;
+        assert_eq!(&frame[center..center + 4], &[255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn converts_premultiplied_bgra_to_straight_rgba() {
+        let bgra = [0, 64, 128, 128, 10, 20, 30, 0];
+        assert_eq!(straight_rgba(&bgra), [255, 128, 0, 128, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn encodes_animated_gif() {
+        let frames = vec![vec![255u8; 4 * 4 * 4], vec![0u8; 4 * 4 * 4]];
+        let gif = encode_gif(&frames, 4, Duration::from_millis(100)).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert!(encode_gif(&[], 4, Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn encodes_png_signature() {
+        let png = encode_png(&[0u8; 2 * 2 * 4], 2).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
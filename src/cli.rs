@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use crate::config::ConfigFormat;
+use crate::platform::JumpTask;
+
+const DEFAULT_BENCH_FRAMES: usize = 500;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    pub config_path: Option<PathBuf>,
+    pub config_format: Option<ConfigFormat>,
+    /// 启动时显示悬浮球渲染调试层（运行中可按 F12 切换）。
+    pub debug_overlay: bool,
+    /// 生成诊断包到该目录后退出，不启动悬浮球。
+    pub diagnostics: Option<PathBuf>,
+    /// 离屏渲染指定帧数并输出耗时统计后退出（开发用，不在帮助中列出）。
+    pub bench_render: Option<usize>,
+    /// 任务栏跳转列表启动的任务，转交给已运行的实例。
+    pub task: Option<JumpTask>,
+}
+
+impl CliArgs {
+    pub fn parse() -> Result<Self, String> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    pub fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+
+            match flag.as_str() {
+                "--config" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .filter(|value| !value.trim().is_empty())
+                        .ok_or("--config requires a file path")?;
+                    parsed.config_path = Some(PathBuf::from(value));
+                }
+                "--config-format" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or("--config-format requires toml or json")?;
+                    let format = ConfigFormat::parse_name(&value)
+                        .ok_or_else(|| format!("unsupported config format: {value}"))?;
+                    parsed.config_format = Some(format);
+                }
+                "--debug-overlay" if inline_value.is_none() => parsed.debug_overlay = true,
+                "--diagnostics" => {
+                    let dir = inline_value.filter(|value| !value.trim().is_empty());
+                    parsed.diagnostics = Some(PathBuf::from(dir.unwrap_or_else(|| ".".into())));
+                }
+                "--bench-render" => {
+                    let frames = match inline_value {
+                        Some(value) => value
+                            .trim()
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|frames| *frames > 0)
+                            .ok_or_else(|| format!("invalid frame count: {value}"))?,
+                        None => DEFAULT_BENCH_FRAMES,
+                    };
+                    parsed.bench_render = Some(frames);
+                }
+                "--task" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or("--task requires refresh, settings or pause")?;
+                    let task = JumpTask::parse_name(&value)
+                        .ok_or_else(|| format!("unknown task: {value}"))?;
+                    parsed.task = Some(task);
+                }
+                other => return Err(format!("unknown argument: {other}")),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::CliArgs;
+    use crate::config::ConfigFormat;
+    use crate::platform::JumpTask;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_empty_args() {
+        assert_eq!(parse(&[]), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn parses_config_path_forms() {
+        let expected = Some(PathBuf::from("work.toml"));
+        assert_eq!(
+            parse(&["--config", "work.toml"]).unwrap().config_path,
+            expected
+        );
+        assert_eq!(
+            parse(&["--config=work.toml"]).unwrap().config_path,
+            expected
+        );
+    }
+
+    #[test]
+    fn parses_config_format() {
+        let args = parse(&["--config-format", "JSON"]).unwrap();
+        assert_eq!(args.config_format, Some(ConfigFormat::Json));
+        assert!(parse(&["--config-format=yaml"]).is_err());
+    }
+
+    #[test]
+    fn parses_debug_overlay_flag() {
+        assert!(parse(&["--debug-overlay"]).unwrap().debug_overlay);
+        assert!(!parse(&[]).unwrap().debug_overlay);
+        assert!(parse(&["--debug-overlay=1"]).is_err());
+    }
+
+    #[test]
+    fn parses_diagnostics_directory() {
+        assert_eq!(
+            parse(&["--diagnostics"]).unwrap().diagnostics,
+            Some(PathBuf::from("."))
+        );
+        assert_eq!(
+            parse(&["--diagnostics=/tmp/support"]).unwrap().diagnostics,
+            Some(PathBuf::from("/tmp/support"))
+        );
+        assert_eq!(parse(&[]).unwrap().diagnostics, None);
+    }
+
+    #[test]
+    fn parses_bench_render_frames() {
+        assert_eq!(parse(&["--bench-render"]).unwrap().bench_render, Some(500));
+        assert_eq!(
+            parse(&["--bench-render=2000"]).unwrap().bench_render,
+            Some(2000)
+        );
+        assert!(parse(&["--bench-render=0"]).is_err());
+    }
+
+    #[test]
+    fn parses_jump_list_task() {
+        assert_eq!(
+            parse(&["--task", "refresh"]).unwrap().task,
+            Some(JumpTask::Refresh)
+        );
+        assert_eq!(
+            parse(&["--task=Pause"]).unwrap().task,
+            Some(JumpTask::Pause)
+        );
+        assert!(parse(&["--task=exit"]).is_err());
+        assert!(parse(&["--task"]).is_err());
+    }
+
+    #[test]
+    fn rejects_missing_config_value() {
+        assert!(parse(&["--config"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_arguments() {
+        assert!(parse(&["--verbose"]).is_err());
+    }
+}
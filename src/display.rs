@@ -0,0 +1,259 @@
+//! 悬浮球显示管线：数据源 → [`DisplayItem`] → [`Formatter`] → 标题、数值、水位与状态。
+
+use std::time::Duration;
+
+use crate::api::{DataAnomaly, Subscription, format_quota, remaining_ratio, validate_subscription};
+use crate::ball::BallStatus;
+
+/// 与来源无关的一项显示数据。
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayItem {
+    pub title: String,
+    pub remaining: f64,
+    pub total: f64,
+    pub unit: Option<String>,
+    /// 水位（0-1），可能经过平滑。
+    pub ratio: f32,
+    /// 数值为手动校正的值。
+    pub corrected: bool,
+    /// 原始数据的异常，校正后仍保留。
+    pub anomaly: Option<DataAnomaly>,
+    /// 数值为两次刷新之间按消耗速度推算的估计值。
+    pub estimated: bool,
+}
+
+/// 能在悬浮球上显示的数据源。
+pub trait DisplaySource {
+    fn display_item(&self) -> DisplayItem;
+}
+
+impl DisplaySource for Subscription {
+    fn display_item(&self) -> DisplayItem {
+        DisplayItem {
+            title: self.name.clone(),
+            remaining: self.remaining_quota,
+            total: self.total_quota,
+            unit: self.unit.clone(),
+            ratio: remaining_ratio(self),
+            corrected: false,
+            anomaly: validate_subscription(self).err(),
+            estimated: false,
+        }
+    }
+}
+
+impl DisplayItem {
+    /// 用手动校正后的订阅替换数值与水位。
+    pub fn corrected_by(self, corrected: &Subscription) -> Self {
+        Self {
+            remaining: corrected.remaining_quota,
+            ratio: remaining_ratio(corrected),
+            corrected: true,
+            ..self
+        }
+    }
+
+    /// 在标题前加上用户为订阅设置的标签。
+    pub fn tagged(self, tag: Option<&str>) -> Self {
+        match tag {
+            Some(tag) => Self {
+                title: format!("{tag} {}", self.title),
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    /// 使用平滑后的水位，没有平滑值时保持原值。
+    pub fn smoothed(self, ratio: Option<f32>) -> Self {
+        Self {
+            ratio: ratio.unwrap_or(self.ratio),
+            ..self
+        }
+    }
+
+    /// 按每小时消耗 `rate` 推算 `elapsed` 之后的剩余额度，不低于 0。
+    pub fn projected(self, rate: f64, elapsed: Duration) -> Self {
+        let used = rate * elapsed.as_secs_f64() / 3600.0;
+        if used <= 0.0 || !used.is_finite() {
+            return self;
+        }
+        let remaining = (self.remaining - used).max(0.0);
+        let ratio = if self.total > 0.0 {
+            (remaining / self.total).clamp(0.0, 1.0) as f32
+        } else {
+            self.ratio
+        };
+        Self {
+            remaining,
+            ratio,
+            estimated: true,
+            ..self
+        }
+    }
+
+    /// 估计值带 `~` 前缀。
+    pub fn format_remaining(&self) -> String {
+        let value = format_quota(self.remaining, self.unit.as_deref());
+        if self.estimated {
+            format!("~{value}")
+        } else {
+            value
+        }
+    }
+}
+
+/// 格式化结果。
+#[derive(Debug, Clone)]
+pub struct Formatted {
+    pub title: String,
+    pub value: String,
+    pub ratio: f32,
+    pub status: BallStatus,
+    pub corrected: bool,
+}
+
+/// 影响显示的全局状态；提示与状态按固定优先级覆盖数值。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Formatter {
+    pub configured: bool,
+    pub credential_issue: bool,
+    pub needs_reauth: bool,
+    pub fetching: bool,
+    pub error: bool,
+    pub spike: bool,
+    pub over_cap: bool,
+}
+
+impl Formatter {
+    pub fn format(&self, item: Option<&DisplayItem>) -> Formatted {
+        let (title, value, ratio) = match item {
+            _ if !self.configured => ("未配置".to_string(), "点右上设置".to_string(), 0.0),
+            _ if self.credential_issue => ("凭证失效".to_string(), "--".to_string(), 0.0),
+            _ if self.needs_reauth => ("Cookie 失效".to_string(), "需重新验证".to_string(), 0.0),
+            Some(item) if item.anomaly.is_some() => {
+                (item.title.clone(), "数据异常".to_string(), 0.0)
+            }
+            Some(item) => (item.title.clone(), item.format_remaining(), item.ratio),
+            None => ("无订阅".to_string(), "0.00".to_string(), 0.0),
+        };
+
+        let anomaly = item.is_some_and(|item| item.anomaly.is_some());
+        let status = if self.fetching {
+            BallStatus::Fetching
+        } else if self.needs_reauth || self.credential_issue {
+            BallStatus::ReAuth
+        } else if self.error {
+            BallStatus::Error
+        } else if anomaly {
+            BallStatus::Anomaly
+        } else if self.spike {
+            BallStatus::Spike
+        } else if self.over_cap {
+            BallStatus::OverCap
+        } else {
+            BallStatus::Idle
+        };
+
+        let shows_value = self.configured
+            && !self.credential_issue
+            && !self.needs_reauth
+            && item.is_some_and(|item| item.anomaly.is_none());
+
+        Formatted {
+            title,
+            value: if self.fetching {
+                "...".to_string()
+            } else {
+                value
+            },
+            ratio,
+            status,
+            corrected: shows_value && item.is_some_and(|item| item.corrected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{DisplaySource, Formatter};
+    use crate::api::Subscription;
+    use crate::ball::BallStatus;
+
+    fn dollars(remaining: f64) -> Subscription {
+        Subscription {
+            unit: Some("$".to_string()),
+            ..Subscription::fixture("Pro", remaining)
+        }
+    }
+
+    #[test]
+    fn formats_item_and_prefers_correction_over_smoothing() {
+        let formatter = Formatter {
+            configured: true,
+            ..Formatter::default()
+        };
+
+        let item = dollars(40.0).display_item().smoothed(Some(0.5));
+        let formatted = formatter.format(Some(&item));
+        assert_eq!(formatted.value, "$40.00");
+        assert_eq!(formatted.ratio, 0.5);
+        assert!(!formatted.corrected);
+
+        let item = dollars(40.0).display_item().corrected_by(&dollars(25.0));
+        let formatted = formatter.format(Some(&item));
+        assert_eq!(formatted.value, "$25.00");
+        assert_eq!(formatted.ratio, 0.25);
+        assert!(formatted.corrected);
+    }
+
+    #[test]
+    fn projects_countdown_with_marker() {
+        let formatter = Formatter {
+            configured: true,
+            ..Formatter::default()
+        };
+
+        let item = dollars(40.0)
+            .display_item()
+            .projected(12.0, Duration::from_secs(15 * 60));
+        let formatted = formatter.format(Some(&item));
+        assert_eq!(formatted.value, "~$37.00");
+        assert_eq!(formatted.ratio, 0.37);
+
+        // 没有消耗时保持实际值，估算不会低于 0
+        let idle = dollars(40.0)
+            .display_item()
+            .projected(0.0, Duration::from_secs(60));
+        assert_eq!(formatter.format(Some(&idle)).value, "$40.00");
+        let drained = dollars(1.0)
+            .display_item()
+            .projected(100.0, Duration::from_secs(3600));
+        assert_eq!(drained.remaining, 0.0);
+    }
+
+    #[test]
+    fn anomalies_and_credentials_override_the_value() {
+        let broken = dollars(120.0).display_item();
+        let formatted = Formatter {
+            configured: true,
+            ..Formatter::default()
+        }
+        .format(Some(&broken));
+        assert_eq!(formatted.value, "数据异常");
+        assert!(matches!(formatted.status, BallStatus::Anomaly));
+
+        let formatted = Formatter {
+            configured: true,
+            credential_issue: true,
+            fetching: true,
+            ..Formatter::default()
+        }
+        .format(Some(&dollars(40.0).display_item()));
+        assert_eq!(formatted.title, "凭证失效");
+        assert_eq!(formatted.value, "...");
+        assert!(matches!(formatted.status, BallStatus::Fetching));
+    }
+}
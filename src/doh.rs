@@ -0,0 +1,158 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::Deserialize;
+
+const CLOUDFLARE_DOH: &str = "https://1.1.1.1/dns-query";
+const GOOGLE_DOH: &str = "https://8.8.8.8/resolve";
+
+/// 解析只是请求的第一步，超时要比整个请求短。
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+const RECORD_A: u16 = 1;
+const RECORD_AAAA: u16 = 28;
+
+/// 将配置值解析为 DoH 地址：支持 `cloudflare`、`google` 预设或自定义 `https://` 地址，空值表示使用系统 DNS。
+pub fn endpoint_for(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    match trimmed.to_ascii_lowercase().as_str() {
+        "" => None,
+        "cloudflare" => Some(CLOUDFLARE_DOH.to_string()),
+        "google" => Some(GOOGLE_DOH.to_string()),
+        lower if lower.starts_with("https://") => Some(trimmed.to_string()),
+        _ => None,
+    }
+}
+
+pub fn is_valid_setting(input: &str) -> bool {
+    input.trim().is_empty() || endpoint_for(input).is_some()
+}
+
+/// 通过 DNS-over-HTTPS（JSON 格式，Cloudflare / Google 通用）解析域名，绕开被污染的本地 DNS。
+#[derive(Debug, Clone)]
+pub struct DohResolver {
+    client: reqwest::Client,
+    endpoint: Arc<str>,
+}
+
+impl DohResolver {
+    pub fn new(endpoint: String) -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: reqwest::Client::builder().timeout(LOOKUP_TIMEOUT).build()?,
+            endpoint: endpoint.into(),
+        })
+    }
+
+    async fn lookup(&self, host: &str) -> Result<Vec<IpAddr>, String> {
+        let mut addrs = self.query(host, RECORD_A).await?;
+        if addrs.is_empty() {
+            addrs = self.query(host, RECORD_AAAA).await?;
+        }
+        if addrs.is_empty() {
+            return Err(format!("doh: no address for {host}"));
+        }
+        Ok(addrs)
+    }
+
+    async fn query(&self, host: &str, record: u16) -> Result<Vec<IpAddr>, String> {
+        let record = record.to_string();
+        let raw = self
+            .client
+            .get(&*self.endpoint)
+            .query(&[("name", host), ("type", record.as_str())])
+            .header("Accept", "application/dns-json")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("doh: {e}"))?
+            .text()
+            .await
+            .map_err(|e| format!("doh: {e}"))?;
+
+        parse_answers(&raw)
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let addrs = resolver.lookup(name.as_str()).await?;
+            // 端口由 reqwest 按 URL 覆盖
+            let addrs: Addrs = Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record: u16,
+    data: String,
+}
+
+fn parse_answers(raw: &str) -> Result<Vec<IpAddr>, String> {
+    let response =
+        serde_json::from_str::<DohResponse>(raw).map_err(|e| format!("doh: bad response: {e}"))?;
+    if response.status != 0 {
+        return Err(format!("doh: server returned status {}", response.status));
+    }
+
+    // CNAME 等记录的 data 不是 IP，直接跳过
+    Ok(response
+        .answer
+        .iter()
+        .filter(|answer| answer.record == RECORD_A || answer.record == RECORD_AAAA)
+        .filter_map(|answer| answer.data.parse::<IpAddr>().ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::{endpoint_for, is_valid_setting, parse_answers};
+
+    #[test]
+    fn resolves_presets_and_custom_urls() {
+        assert_eq!(
+            endpoint_for(" Cloudflare ").as_deref(),
+            Some("https://1.1.1.1/dns-query")
+        );
+        assert_eq!(
+            endpoint_for("https://doh.example/dns-query").as_deref(),
+            Some("https://doh.example/dns-query")
+        );
+        assert_eq!(endpoint_for(""), None);
+        assert!(is_valid_setting(""));
+        assert!(!is_valid_setting("http://insecure.example"));
+    }
+
+    #[test]
+    fn parses_a_records_and_skips_cnames() {
+        let raw = r#"{"Status":0,"Answer":[
+            {"name":"right.codes","type":5,"data":"cdn.example."},
+            {"name":"cdn.example","type":1,"data":"104.21.0.1"}
+        ]}"#;
+        assert_eq!(
+            parse_answers(raw),
+            Ok(vec!["104.21.0.1".parse::<IpAddr>().unwrap()])
+        );
+    }
+
+    #[test]
+    fn reports_server_failure() {
+        assert!(parse_answers(r#"{"Status":3}"#).is_err());
+    }
+}
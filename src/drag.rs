@@ -0,0 +1,219 @@
+use std::time::{Duration, Instant};
+
+use iced::{Point, Rectangle, Vector};
+
+/// 离工作区边缘小于该距离（逻辑像素）时吸附到边缘。
+const SNAP_DISTANCE: f32 = 16.0;
+/// 按最近这段时间内的光标位移估算松手速度。
+const VELOCITY_WINDOW: Duration = Duration::from_millis(80);
+/// 松手速度低于该值（逻辑像素/秒）时不滑行。
+const FLING_SPEED: f32 = 400.0;
+/// 惯性滑行的距离相当于按松手速度继续移动这么久。
+const GLIDE: Duration = Duration::from_millis(250);
+/// 左右折返的最小位移、次数与时间窗口，达到后视为摇晃。
+const SHAKE_TRAVEL: f32 = 24.0;
+const SHAKE_REVERSALS: usize = 4;
+const SHAKE_WINDOW: Duration = Duration::from_millis(800);
+
+/// 一次拖动的结果。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragStep {
+    /// 窗口需要移动到的位置；与上次相同时为 `None`。
+    pub position: Option<Point>,
+    pub shaken: bool,
+}
+
+/// 手动拖动：按光标位移移动窗口，而不是交给系统的拖动循环，
+/// 因此拖动中可以吸附边缘、检测摇晃，松手后还能带惯性滑行。
+#[derive(Debug, Clone)]
+pub struct ManualDrag {
+    /// 按下时光标相对窗口左上角的偏移。
+    grab: Vector,
+    position: Point,
+    samples: Vec<(Instant, Point)>,
+    /// 当前水平移动方向（-1/0/1）与该方向上到达的最远处。
+    direction: f32,
+    extreme: f32,
+    reversals: Vec<Instant>,
+}
+
+impl ManualDrag {
+    pub fn new(cursor: Point, window: Point, now: Instant) -> Self {
+        Self {
+            grab: cursor - window,
+            position: window,
+            samples: vec![(now, cursor)],
+            direction: 0.0,
+            extreme: cursor.x,
+            reversals: Vec::new(),
+        }
+    }
+
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    /// 按光标的全局位置推进；`work_area` 为空时不吸附。
+    pub fn update(
+        &mut self,
+        cursor: Point,
+        size: f32,
+        work_area: Option<Rectangle>,
+        now: Instant,
+    ) -> DragStep {
+        self.samples
+            .retain(|(at, _)| now.saturating_duration_since(*at) <= VELOCITY_WINDOW);
+        self.samples.push((now, cursor));
+        let shaken = self.track_shake(cursor.x, now);
+
+        let raw = cursor - self.grab;
+        let position = work_area.map_or(raw, |area| snap(raw, size, area));
+        let moved = position != self.position;
+        self.position = position;
+        DragStep {
+            position: moved.then_some(position),
+            shaken,
+        }
+    }
+
+    /// 松手时的速度（逻辑像素/秒）。
+    pub fn velocity(&self) -> Vector {
+        let (Some((first_at, first)), Some((last_at, last))) =
+            (self.samples.first(), self.samples.last())
+        else {
+            return Vector::ZERO;
+        };
+        let dt = last_at.saturating_duration_since(*first_at).as_secs_f32();
+        if dt <= 0.0 {
+            return Vector::ZERO;
+        }
+        (*last - *first) * (1.0 / dt)
+    }
+
+    /// 松手速度足够快时返回滑行终点（限制在工作区内并吸附边缘）。
+    pub fn glide_target(&self, size: f32, work_area: Option<Rectangle>) -> Option<Point> {
+        let velocity = self.velocity();
+        let speed = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+        if speed < FLING_SPEED {
+            return None;
+        }
+        let target = self.position + velocity * GLIDE.as_secs_f32();
+        Some(work_area.map_or(target, |area| snap(clamp(target, size, area), size, area)))
+    }
+
+    fn track_shake(&mut self, x: f32, now: Instant) -> bool {
+        let dx = x - self.extreme;
+        if self.direction * dx > 0.0 {
+            self.extreme = x;
+            return false;
+        }
+        if dx.abs() < SHAKE_TRAVEL {
+            return false;
+        }
+
+        self.direction = dx.signum();
+        self.extreme = x;
+        self.reversals
+            .retain(|at| now.saturating_duration_since(*at) < SHAKE_WINDOW);
+        self.reversals.push(now);
+        if self.reversals.len() < SHAKE_REVERSALS {
+            return false;
+        }
+        self.reversals.clear();
+        true
+    }
+}
+
+fn snap(position: Point, size: f32, area: Rectangle) -> Point {
+    let right = area.x + area.width - size;
+    let bottom = area.y + area.height - size;
+    let snap_axis = |value: f32, start: f32, end: f32| {
+        if (value - start).abs() < SNAP_DISTANCE {
+            start
+        } else if (value - end).abs() < SNAP_DISTANCE {
+            end
+        } else {
+            value
+        }
+    };
+    Point::new(
+        snap_axis(position.x, area.x, right),
+        snap_axis(position.y, area.y, bottom),
+    )
+}
+
+fn clamp(position: Point, size: f32, area: Rectangle) -> Point {
+    let right = (area.x + area.width - size).max(area.x);
+    let bottom = (area.y + area.height - size).max(area.y);
+    Point::new(
+        position.x.clamp(area.x, right),
+        position.y.clamp(area.y, bottom),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use iced::{Point, Rectangle, Size};
+
+    use super::ManualDrag;
+
+    fn screen() -> Option<Rectangle> {
+        Some(Rectangle::new(Point::ORIGIN, Size::new(1920.0, 1040.0)))
+    }
+
+    #[test]
+    fn follows_the_cursor_and_snaps_to_edges() {
+        let start = Instant::now();
+        let mut drag = ManualDrag::new(Point::new(560.0, 560.0), Point::new(500.0, 500.0), start);
+
+        let step = drag.update(Point::new(600.5, 580.25), 120.0, screen(), start);
+        assert_eq!(step.position, Some(Point::new(540.5, 520.25)));
+        assert!(!step.shaken);
+
+        let step = drag.update(Point::new(70.0, 300.0), 120.0, screen(), start);
+        assert_eq!(step.position, Some(Point::new(0.0, 240.0)));
+        let step = drag.update(Point::new(72.0, 300.0), 120.0, screen(), start);
+        assert_eq!(step.position, None);
+    }
+
+    #[test]
+    fn glides_after_a_fast_release() {
+        let start = Instant::now();
+        let mut drag = ManualDrag::new(Point::new(560.0, 560.0), Point::new(500.0, 500.0), start);
+        drag.update(
+            Point::new(565.0, 560.0),
+            120.0,
+            screen(),
+            start + Duration::from_millis(40),
+        );
+        assert_eq!(drag.glide_target(120.0, screen()), None);
+
+        drag.update(
+            Point::new(700.0, 560.0),
+            120.0,
+            screen(),
+            start + Duration::from_millis(80),
+        );
+        // 80ms 内移动 140 像素（1750 像素/秒），再滑行 437.5 像素
+        let target = drag.glide_target(120.0, screen()).unwrap();
+        assert!((target.x - 1077.5).abs() < 0.1 && target.y == 500.0);
+    }
+
+    #[test]
+    fn detects_a_horizontal_shake() {
+        let start = Instant::now();
+        let mut drag = ManualDrag::new(Point::new(500.0, 500.0), Point::new(440.0, 440.0), start);
+        let xs = [540.0, 480.0, 540.0, 480.0];
+        let shaken: Vec<bool> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let now = start + Duration::from_millis(100 * (i as u64 + 1));
+                drag.update(Point::new(*x, 500.0), 120.0, None, now).shaken
+            })
+            .collect();
+        assert_eq!(shaken, [false, false, false, true]);
+    }
+}
@@ -1,5 +1,11 @@
 use std::future::Future;
 
+/// 后台任务（请求、文件读写）的线程名，便于在调试器与崩溃报告中辨认。
+const THREAD_NAME: &str = "rightcode-worker";
+const WORKER_THREADS: usize = 2;
+
+/// 应用的异步运行时：两个工作线程足够处理刷新请求，比默认按 CPU 核数开线程更省资源。
+/// 由 `app::run` 中的 `.executor::<AppExecutor>()` 选用，所有 `Task::perform` 都在这里执行。
 pub struct AppExecutor {
     runtime: tokio::runtime::Runtime,
 }
@@ -7,7 +13,8 @@ pub struct AppExecutor {
 impl iced::Executor for AppExecutor {
     fn new() -> Result<Self, iced_futures::futures::io::Error> {
         let runtime = tokio::runtime::Builder::new_multi_thread()
-            .worker_threads(2)
+            .worker_threads(WORKER_THREADS)
+            .thread_name(THREAD_NAME)
             .enable_all()
             .build()?;
 
@@ -24,3 +31,34 @@ impl iced::Executor for AppExecutor {
         f()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use iced::Executor;
+
+    use super::{AppExecutor, THREAD_NAME};
+
+    #[test]
+    fn spawned_futures_run_on_named_workers() {
+        let executor = AppExecutor::new().expect("runtime");
+        let (sender, receiver) = mpsc::channel();
+
+        executor.spawn(async move {
+            let _ = sender.send(std::thread::current().name().map(str::to_string));
+        });
+
+        let name = receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("future ran");
+        assert_eq!(name.as_deref(), Some(THREAD_NAME));
+    }
+
+    #[test]
+    fn enter_provides_the_tokio_context() {
+        let executor = AppExecutor::new().expect("runtime");
+        assert!(executor.enter(|| tokio::runtime::Handle::try_current().is_ok()));
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use iced::{Point, Vector};
+
+/// 跟随时窗口左上角相对光标的偏移，避免悬浮球挡住光标。
+pub const CURSOR_OFFSET: Vector = Vector::new(24.0, 24.0);
+
+/// 弹簧刚度与阻尼；阻尼略低于临界值，停下前会轻微回弹。
+const STIFFNESS: f32 = 24.0;
+const DAMPING: f32 = 9.0;
+/// 单步最长积分时间，窗口卡顿后不会一下子甩出很远。
+const MAX_STEP: Duration = Duration::from_millis(100);
+/// 距离与速度都低于该值时视为已停稳，不再移动窗口。
+const SETTLE_EPSILON: f32 = 0.5;
+
+/// 以弹簧模型让悬浮球延迟跟随光标。
+#[derive(Debug, Clone, Copy)]
+pub struct Follower {
+    position: Point,
+    velocity: Vector,
+}
+
+impl Follower {
+    pub fn new(position: Point) -> Self {
+        Self {
+            position,
+            velocity: Vector::ZERO,
+        }
+    }
+
+    /// 带初速度出发，用于松手后的惯性滑行。
+    pub fn with_velocity(position: Point, velocity: Vector) -> Self {
+        Self { position, velocity }
+    }
+
+    /// 向 `target` 前进 `dt`，返回新的窗口位置；已停稳时返回 `None`。
+    pub fn step(&mut self, target: Point, dt: Duration) -> Option<Point> {
+        let offset = target - self.position;
+        if length(offset) < SETTLE_EPSILON && length(self.velocity) < SETTLE_EPSILON {
+            self.velocity = Vector::ZERO;
+            return None;
+        }
+
+        let dt = dt.min(MAX_STEP).as_secs_f32();
+        let acceleration = offset * STIFFNESS - self.velocity * DAMPING;
+        self.velocity = self.velocity + acceleration * dt;
+        self.position = self.position + self.velocity * dt;
+        Some(self.position)
+    }
+}
+
+fn length(vector: Vector) -> f32 {
+    (vector.x * vector.x + vector.y * vector.y).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use iced::Point;
+
+    use super::Follower;
+
+    #[test]
+    fn converges_on_the_target_and_settles() {
+        let mut follower = Follower::new(Point::new(0.0, 0.0));
+        let target = Point::new(300.0, 100.0);
+
+        let first = follower.step(target, Duration::from_millis(33)).unwrap();
+        assert!(first.x > 0.0 && first.x < 30.0, "moves gently: {first:?}");
+
+        let mut last = first;
+        for _ in 0..300 {
+            match follower.step(target, Duration::from_millis(33)) {
+                Some(position) => last = position,
+                None => break,
+            }
+        }
+        assert!((last.x - 300.0).abs() < 1.0 && (last.y - 100.0).abs() < 1.0);
+        assert!(follower.step(target, Duration::from_millis(33)).is_none());
+    }
+
+    #[test]
+    fn clamps_long_frames() {
+        let mut follower = Follower::new(Point::new(0.0, 0.0));
+        let position = follower
+            .step(Point::new(100.0, 0.0), Duration::from_secs(5))
+            .unwrap();
+        assert!(position.x < 100.0);
+    }
+}
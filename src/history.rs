@@ -0,0 +1,454 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{DailyUsage, Subscription};
+use crate::config::ConfigError;
+use crate::{schedule, spike};
+
+/// 历史只保留最近这么多天，启动时压缩 `history.jsonl`。
+const RETENTION_DAYS: u64 = 120;
+const DAY: u64 = 24 * 60 * 60;
+
+/// 历史记录的一行（`history.jsonl`，每行一个 JSON）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HistoryRecord {
+    /// 本地每次刷新成功时的采样。
+    Sample {
+        at: u64,
+        subscription: String,
+        remaining: f64,
+        total: f64,
+    },
+    /// 由用量汇总接口提供的按日用量。
+    Daily {
+        date: NaiveDate,
+        subscription: Option<String>,
+        used: f64,
+    },
+}
+
+/// 解析后的历史：采样按订阅分组并按时间排序，按日用量按 (日期, 订阅) 去重。
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    samples: HashMap<String, Vec<(u64, f64)>>,
+    daily: BTreeMap<(NaiveDate, Option<String>), f64>,
+    /// 各订阅最近的消耗速度，只在采样变化时重算，供每秒更新的模拟倒计时读取。
+    recent_rates: HashMap<String, f64>,
+}
+
+impl History {
+    /// 同一 (日期, 订阅) 的按日用量以后写入的为准。
+    pub fn new(records: Vec<HistoryRecord>) -> Self {
+        let mut history = Self::default();
+        for record in records {
+            match record {
+                HistoryRecord::Sample {
+                    at,
+                    subscription,
+                    remaining,
+                    ..
+                } => history
+                    .samples
+                    .entry(subscription)
+                    .or_default()
+                    .push((at, remaining)),
+                HistoryRecord::Daily {
+                    date,
+                    subscription,
+                    used,
+                } => {
+                    history.daily.insert((date, subscription), used);
+                }
+            }
+        }
+        for samples in history.samples.values_mut() {
+            samples.sort_by_key(|(at, _)| *at);
+        }
+        let names: Vec<String> = history.samples.keys().cloned().collect();
+        for name in names {
+            history.update_recent_rate(&name);
+        }
+        history
+    }
+
+    /// 记录一次刷新结果，返回需要追加写入的记录。
+    pub fn record_samples(
+        &mut self,
+        at: u64,
+        subscriptions: &[Subscription],
+    ) -> Vec<HistoryRecord> {
+        for sub in subscriptions {
+            let samples = self.samples.entry(sub.name.clone()).or_default();
+            let index = samples.partition_point(|(time, _)| *time <= at);
+            samples.insert(index, (at, sub.remaining_quota));
+            // 长时间运行时内存中同样只保留保留期内的采样
+            let expired = samples.partition_point(|(time, _)| *time < retention_start(at));
+            samples.drain(..expired);
+            self.update_recent_rate(&sub.name);
+        }
+        subscriptions
+            .iter()
+            .map(|sub| HistoryRecord::Sample {
+                at,
+                subscription: sub.name.clone(),
+                remaining: sub.remaining_quota,
+                total: sub.total_quota,
+            })
+            .collect()
+    }
+
+    /// 用接口返回的按日用量更新历史：新的日期与数值有变化的日期（通常是今天）都写入，
+    /// 返回需要追加的记录。
+    pub fn seed_daily(&mut self, usage: &[DailyUsage]) -> Vec<HistoryRecord> {
+        let mut added = Vec::new();
+        for day in usage {
+            let key = (day.date, day.subscription.clone());
+            if self.daily.get(&key) == Some(&day.used) {
+                continue;
+            }
+            self.daily.insert(key, day.used);
+            added.push(HistoryRecord::Daily {
+                date: day.date,
+                subscription: day.subscription.clone(),
+                used: day.used,
+            });
+        }
+        added
+    }
+
+    /// 某个订阅的按日用量：接口数据优先（该订阅自己的数据优先于不区分订阅的汇总），
+    /// 其余日期由本地采样中剩余额度的下降量累计。
+    pub fn daily_usage(&self, subscription: &str, timezone: &str) -> BTreeMap<NaiveDate, f64> {
+        let mut local: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        let mut previous: Option<f64> = None;
+
+        for &(at, remaining) in self.samples(subscription) {
+            if let Some(previous) = previous {
+                // 额度上升（充值/重置）不计入用量
+                let used = (previous - remaining).max(0.0);
+                *local
+                    .entry(schedule::local_date_of(at, timezone))
+                    .or_default() += used;
+            }
+            previous = Some(remaining);
+        }
+
+        for ((date, name), used) in &self.daily {
+            if name.is_none() {
+                local.insert(*date, *used);
+            }
+        }
+        for ((date, name), used) in &self.daily {
+            if name.as_deref() == Some(subscription) {
+                local.insert(*date, *used);
+            }
+        }
+
+        local
+    }
+
+    /// 今天截至 `now` 的用量减去昨天截至同一时刻的用量（只看本地采样）；
+    /// 昨天这段时间没有采样时为 `None`。
+    pub fn usage_vs_yesterday(&self, subscription: &str, now: u64, timezone: &str) -> Option<f64> {
+        let today_start = now - schedule::seconds_since_midnight(now, timezone);
+        let yesterday_start = today_start.saturating_sub(DAY);
+        let yesterday_now = now.saturating_sub(DAY);
+
+        let samples = self.samples(subscription);
+        if !samples
+            .iter()
+            .any(|(at, _)| (yesterday_start..=yesterday_now).contains(at))
+        {
+            return None;
+        }
+        Some(
+            used_between(samples, today_start, now)
+                - used_between(samples, yesterday_start, yesterday_now),
+        )
+    }
+
+    /// `(start, end]` 内的用量（只看本地采样，额度上升不计入）。
+    pub fn usage_between(&self, subscription: &str, start: u64, end: u64) -> f64 {
+        used_between(self.samples(subscription), start, end)
+    }
+
+    /// 某个订阅按时间排序的采样（时间, 剩余额度）。
+    pub fn samples(&self, subscription: &str) -> &[(u64, f64)] {
+        self.samples.get(subscription).map_or(&[], Vec::as_slice)
+    }
+
+    /// 某个订阅最近几个间隔的平均消耗速度（额度/小时）。
+    pub fn recent_rate(&self, subscription: &str) -> Option<f64> {
+        self.recent_rates.get(subscription).copied()
+    }
+
+    fn update_recent_rate(&mut self, subscription: &str) {
+        match spike::recent_rate(self.samples(subscription)) {
+            Some(rate) => self.recent_rates.insert(subscription.to_string(), rate),
+            None => self.recent_rates.remove(subscription),
+        };
+    }
+}
+
+fn retention_start(now: u64) -> u64 {
+    now.saturating_sub(RETENTION_DAYS * DAY)
+}
+
+/// 丢弃保留期之外的记录，同一 (日期, 订阅) 的按日用量只留最后一条。
+fn compact(records: Vec<HistoryRecord>, now: u64) -> Vec<HistoryRecord> {
+    let start = retention_start(now);
+    let first_date = chrono::DateTime::from_timestamp(start as i64, 0)
+        .map(|time| time.date_naive())
+        .unwrap_or_default();
+    let mut latest_daily = HashMap::new();
+    for (index, record) in records.iter().enumerate() {
+        if let HistoryRecord::Daily {
+            date, subscription, ..
+        } = record
+        {
+            latest_daily.insert((*date, subscription.clone()), index);
+        }
+    }
+
+    records
+        .into_iter()
+        .enumerate()
+        .filter(|(index, record)| match record {
+            HistoryRecord::Sample { at, .. } => *at >= start,
+            HistoryRecord::Daily {
+                date, subscription, ..
+            } => {
+                *date >= first_date
+                    && latest_daily.get(&(*date, subscription.clone())) == Some(index)
+            }
+        })
+        .map(|(_, record)| record)
+        .collect()
+}
+
+/// 落在 `(start, end]` 内的采样相对前一次采样的额度下降量之和，额度上升不计入。
+fn used_between(samples: &[(u64, f64)], start: u64, end: u64) -> f64 {
+    samples
+        .windows(2)
+        .filter(|pair| pair[1].0 > start && pair[1].0 <= end)
+        .map(|pair| (pair[0].1 - pair[1].1).max(0.0))
+        .sum()
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    pub path: PathBuf,
+}
+
+impl HistoryStore {
+    /// 读取全部记录，无法解析的行直接跳过。
+    pub fn load(&self) -> Result<Vec<HistoryRecord>, ConfigError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = std::fs::read_to_string(&self.path)?;
+        Ok(raw
+            .lines()
+            .filter_map(|line| serde_json::from_str::<HistoryRecord>(line).ok())
+            .collect())
+    }
+
+    /// 读取记录并压缩：有记录被丢弃时重写文件，避免历史文件无限增长。
+    pub fn load_compacted(&self, now: u64) -> Result<Vec<HistoryRecord>, ConfigError> {
+        let records = self.load()?;
+        let total = records.len();
+        let records = compact(records, now);
+        if records.len() < total {
+            let mut raw = String::new();
+            for record in &records {
+                raw.push_str(&serde_json::to_string(record)?);
+                raw.push('\n');
+            }
+            std::fs::write(&self.path, raw)?;
+        }
+        Ok(records)
+    }
+
+    pub fn append(&self, records: &[HistoryRecord]) -> Result<(), ConfigError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        for record in records {
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{DAY, History, HistoryRecord, RETENTION_DAYS, compact};
+    use crate::api::DailyUsage;
+
+    fn sample(at: u64, remaining: f64) -> HistoryRecord {
+        HistoryRecord::Sample {
+            at,
+            subscription: "plan".to_string(),
+            remaining,
+            total: 100.0,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn derives_daily_usage_from_samples() {
+        // 2026-10-01 00:00:00 UTC
+        let day1 = 1_790_812_800;
+        let history = History::new(vec![
+            sample(day1 + 3600, 100.0),
+            sample(day1 + 7200, 90.0),
+            sample(day1 + 86_400 + 3600, 70.0),
+            // 充值不计入用量
+            sample(day1 + 86_400 + 7200, 100.0),
+        ]);
+
+        let usage = history.daily_usage("plan", "+00:00");
+        assert_eq!(usage.get(&date(1)), Some(&10.0));
+        assert_eq!(usage.get(&date(2)), Some(&20.0));
+    }
+
+    #[test]
+    fn compares_usage_with_the_same_time_yesterday() {
+        // 2026-10-01 00:00:00 UTC
+        let day1 = 1_790_812_800;
+        let history = History::new(vec![
+            sample(day1 + 3600, 100.0),
+            sample(day1 + 7200, 95.0),
+            // 昨天同一时刻之后的用量不计入
+            sample(day1 + 36_000, 80.0),
+            sample(day1 + 86_400 + 3600, 80.0),
+            sample(day1 + 86_400 + 7200, 72.0),
+        ]);
+
+        let now = day1 + 86_400 + 7200;
+        assert_eq!(history.usage_vs_yesterday("plan", now, "+00:00"), Some(3.0));
+        // 前一天没有采样
+        assert_eq!(
+            history.usage_vs_yesterday("plan", day1 + 7200, "+00:00"),
+            None
+        );
+    }
+
+    #[test]
+    fn sums_usage_within_a_window() {
+        let history = History::new(vec![
+            sample(100, 50.0),
+            sample(200, 45.0),
+            sample(300, 60.0),
+            sample(400, 58.0),
+        ]);
+        assert_eq!(history.usage_between("plan", 150, 400), 7.0);
+        assert_eq!(history.usage_between("plan", 250, 400), 2.0);
+    }
+
+    #[test]
+    fn seeded_daily_usage_takes_precedence_and_is_not_duplicated() {
+        let mut history = History::default();
+        let usage = vec![DailyUsage {
+            date: date(1),
+            subscription: None,
+            used: 12.5,
+        }];
+
+        assert_eq!(history.seed_daily(&usage).len(), 1);
+        assert!(history.seed_daily(&usage).is_empty());
+        assert_eq!(
+            history.daily_usage("plan", "+00:00").get(&date(1)),
+            Some(&12.5)
+        );
+    }
+
+    #[test]
+    fn seeding_updates_changed_days_and_prefers_per_subscription_usage() {
+        let mut history = History::default();
+        let day = |subscription: Option<&str>, used| DailyUsage {
+            date: date(1),
+            subscription: subscription.map(str::to_string),
+            used,
+        };
+
+        history.seed_daily(&[day(None, 3.0), day(Some("plan"), 2.0)]);
+        // 今天的用量随后增加，需要覆盖旧值
+        assert_eq!(history.seed_daily(&[day(Some("plan"), 5.0)]).len(), 1);
+        assert_eq!(
+            history.daily_usage("plan", "+00:00").get(&date(1)),
+            Some(&5.0)
+        );
+        assert_eq!(
+            history.daily_usage("other", "+00:00").get(&date(1)),
+            Some(&3.0)
+        );
+    }
+
+    #[test]
+    fn keeps_samples_sorted_when_loaded_out_of_order() {
+        let history = History::new(vec![sample(300, 40.0), sample(100, 50.0)]);
+        assert_eq!(history.samples("plan"), [(100, 50.0), (300, 40.0)]);
+        assert!(history.samples("missing").is_empty());
+    }
+
+    #[test]
+    fn caches_the_recent_burn_rate_per_subscription() {
+        let history = History::new(vec![sample(0, 100.0), sample(3600, 98.0)]);
+        assert_eq!(history.recent_rate("plan"), Some(2.0));
+        assert_eq!(history.recent_rate("missing"), None);
+    }
+
+    #[test]
+    fn compaction_drops_expired_samples_and_superseded_daily_usage() {
+        let now = 1_790_812_800;
+        let daily = |used| HistoryRecord::Daily {
+            date: date(1),
+            subscription: None,
+            used,
+        };
+        let records = vec![
+            sample(now - (RETENTION_DAYS + 1) * DAY, 90.0),
+            daily(1.0),
+            sample(now - DAY, 80.0),
+            daily(2.0),
+        ];
+
+        assert_eq!(
+            compact(records, now),
+            vec![sample(now - DAY, 80.0), daily(2.0)]
+        );
+    }
+
+    #[test]
+    fn records_round_trip_as_json_lines() {
+        let record = HistoryRecord::Daily {
+            date: date(3),
+            subscription: Some("plan".to_string()),
+            used: 1.0,
+        };
+        let line = serde_json::to_string(&record).unwrap();
+        assert!(line.contains(r#""kind":"daily""#));
+        assert_eq!(
+            serde_json::from_str::<HistoryRecord>(&line).unwrap(),
+            record
+        );
+    }
+}
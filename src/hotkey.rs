@@ -0,0 +1,138 @@
+//! 全局快捷键：在任何程序中按下即可显示/隐藏悬浮球、刷新或打开设置。
+
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+use crate::bus::{self, Command};
+use crate::config::HotkeyConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    ToggleBall,
+    Refresh,
+    Settings,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 3] = [
+        HotkeyAction::ToggleBall,
+        HotkeyAction::Refresh,
+        HotkeyAction::Settings,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HotkeyAction::ToggleBall => "显示/隐藏",
+            HotkeyAction::Refresh => "刷新",
+            HotkeyAction::Settings => "设置",
+        }
+    }
+
+    pub fn binding(self, config: &HotkeyConfig) -> &str {
+        match self {
+            HotkeyAction::ToggleBall => &config.toggle_ball,
+            HotkeyAction::Refresh => &config.refresh,
+            HotkeyAction::Settings => &config.settings,
+        }
+    }
+
+    pub fn binding_mut(self, config: &mut HotkeyConfig) -> &mut String {
+        match self {
+            HotkeyAction::ToggleBall => &mut config.toggle_ball,
+            HotkeyAction::Refresh => &mut config.refresh,
+            HotkeyAction::Settings => &mut config.settings,
+        }
+    }
+}
+
+/// 解析 `Ctrl+Alt+Q` 形式的组合键（不区分大小写），留空表示不启用。
+pub fn parse(input: &str) -> Result<Option<HotKey>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    input
+        .parse::<HotKey>()
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+/// 已向系统注册的快捷键；按下时经命令总线转为 `Command::Hotkey`。
+pub struct Hotkeys {
+    manager: GlobalHotKeyManager,
+    registered: Vec<(HotKey, HotkeyAction)>,
+}
+
+impl Hotkeys {
+    pub fn new() -> Result<Self, String> {
+        let manager = GlobalHotKeyManager::new().map_err(|err| err.to_string())?;
+        GlobalHotKeyEvent::set_event_handler(Some(|event: GlobalHotKeyEvent| {
+            if event.state == HotKeyState::Pressed {
+                bus::send(Command::Hotkey(event.id));
+            }
+        }));
+        Ok(Self {
+            manager,
+            registered: Vec::new(),
+        })
+    }
+
+    /// 按配置重新注册，返回无法解析或已被其他程序占用的快捷键说明。
+    pub fn apply(&mut self, config: &HotkeyConfig) -> Vec<String> {
+        for (hotkey, _) in self.registered.drain(..) {
+            let _ = self.manager.unregister(hotkey);
+        }
+
+        let mut errors = Vec::new();
+        for action in HotkeyAction::ALL {
+            let binding = action.binding(config);
+            let result = parse(binding).and_then(|hotkey| match hotkey {
+                Some(hotkey) => self
+                    .manager
+                    .register(hotkey)
+                    .map(|()| Some(hotkey))
+                    .map_err(|err| err.to_string()),
+                None => Ok(None),
+            });
+            match result {
+                Ok(Some(hotkey)) => self.registered.push((hotkey, action)),
+                Ok(None) => {}
+                Err(err) => errors.push(format!("{}（{}）: {err}", action.label(), binding.trim())),
+            }
+        }
+        errors
+    }
+
+    pub fn action(&self, id: u32) -> Option<HotkeyAction> {
+        self.registered
+            .iter()
+            .find_map(|(hotkey, action)| (hotkey.id() == id).then_some(*action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HotkeyAction, parse};
+    use crate::config::HotkeyConfig;
+
+    #[test]
+    fn parses_default_bindings() {
+        let config = HotkeyConfig::default();
+        let ids: Vec<u32> = HotkeyAction::ALL
+            .into_iter()
+            .map(|action| parse(action.binding(&config)).unwrap().unwrap().id())
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids[0] != ids[1] && ids[1] != ids[2]);
+        assert_eq!(
+            parse("ctrl+alt+q").unwrap().map(|hotkey| hotkey.id()),
+            Some(ids[0])
+        );
+    }
+
+    #[test]
+    fn empty_binding_disables_and_garbage_is_rejected() {
+        assert_eq!(parse("  "), Ok(None));
+        assert!(parse("Ctrl+Alt+NotAKey").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use chacha20poly1305::aead::OsRng;
+
+/// 设置页密码的最短长度（字符）。
+const MIN_PIN_LEN: usize = 4;
+const KEYCHAIN_SERVICE: &str = "rightcode-floatingball";
+
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error("keychain: {0}")]
+    Keychain(#[from] keyring::Error),
+    #[error("hash: {0}")]
+    Hash(String),
+}
+
+pub fn is_valid_pin(pin: &str) -> bool {
+    pin.chars().count() >= MIN_PIN_LEN
+}
+
+/// Argon2id 加盐哈希，输出 PHC 字符串。
+pub fn hash_pin(pin: &str) -> Result<String, LockError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| LockError::Hash(err.to_string()))
+}
+
+pub fn verify_pin(pin: &str, hash: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(pin.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+/// 系统钥匙串中保存的密码哈希，按配置文件区分，多实例互不影响。
+#[derive(Debug, Clone)]
+pub struct PinStore {
+    account: String,
+}
+
+impl PinStore {
+    pub fn new(config_path: &str) -> Self {
+        Self {
+            account: format!("settings-pin:{config_path}"),
+        }
+    }
+
+    fn entry(&self) -> Result<keyring::Entry, LockError> {
+        Ok(keyring::Entry::new(KEYCHAIN_SERVICE, &self.account)?)
+    }
+
+    pub fn save(&self, pin: &str) -> Result<(), LockError> {
+        let hash = hash_pin(pin)?;
+        self.entry()?.set_password(&hash)?;
+        Ok(())
+    }
+
+    /// 钥匙串中没有密码时返回 `None`（例如 Linux 内核密钥环在重启后被清空）。
+    pub fn verify(&self, pin: &str) -> Result<Option<bool>, LockError> {
+        match self.entry()?.get_password() {
+            Ok(hash) => Ok(Some(verify_pin(pin, &hash))),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn clear(&self) -> Result<(), LockError> {
+        match self.entry()?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_pin, is_valid_pin, verify_pin};
+
+    #[test]
+    fn hashes_and_verifies_pins() {
+        assert!(!is_valid_pin("123"));
+        assert!(is_valid_pin("1234"));
+
+        let hash = hash_pin("2468").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert_ne!(hash, hash_pin("2468").unwrap());
+        assert!(verify_pin("2468", &hash));
+        assert!(!verify_pin("2469", &hash));
+        assert!(!verify_pin("2468", "not-a-hash"));
+    }
+}
@@ -0,0 +1,122 @@
+//! 低额度提醒：剩余比例跌破用户设置的阈值时弹出系统通知。
+
+use std::collections::HashMap;
+
+use crate::api::{Subscription, validate_subscription};
+
+/// 提醒过后，剩余比例回升超过阈值这么多个百分点才重新提醒，避免在阈值附近来回抖动。
+const REARM_MARGIN: f64 = 2.0;
+
+/// 一次需要发出的提醒。
+#[derive(Debug, Clone, PartialEq)]
+pub struct LowQuota {
+    pub name: String,
+    pub percent: f64,
+    pub threshold: u32,
+}
+
+/// 记录每个订阅已提醒过的最低阈值，同一阈值只提醒一次，跌破更低的阈值或回升后再提醒。
+#[derive(Debug, Clone, Default)]
+pub struct LowQuotaAlerts {
+    notified: HashMap<String, u32>,
+}
+
+impl LowQuotaAlerts {
+    /// 返回本次刷新新跌破阈值的订阅；总额度为 0 或数据异常的订阅不参与。
+    pub fn check(&mut self, subscriptions: &[Subscription], thresholds: &[u32]) -> Vec<LowQuota> {
+        if thresholds.is_empty() {
+            self.notified.clear();
+            return Vec::new();
+        }
+
+        let mut alerts = Vec::new();
+        for sub in subscriptions {
+            if sub.total_quota <= 0.0 || validate_subscription(sub).is_err() {
+                continue;
+            }
+            let percent = sub.remaining_quota / sub.total_quota * 100.0;
+            let level = thresholds
+                .iter()
+                .copied()
+                .filter(|threshold| percent < f64::from(*threshold))
+                .min();
+
+            match self.notified.get(&sub.name).copied() {
+                Some(notified) if percent >= f64::from(notified) + REARM_MARGIN => {
+                    // 已回升：记到当前所在的档位（不再提醒），之后跌破更低的档位仍会提醒
+                    match level {
+                        Some(level) => self.notified.insert(sub.name.clone(), level),
+                        None => self.notified.remove(&sub.name),
+                    };
+                }
+                Some(notified) if level.is_none_or(|level| level >= notified) => {}
+                _ => {
+                    if let Some(level) = level {
+                        self.notified.insert(sub.name.clone(), level);
+                        alerts.push(LowQuota {
+                            name: sub.name.clone(),
+                            percent,
+                            threshold: level,
+                        });
+                    }
+                }
+            }
+        }
+        alerts
+    }
+}
+
+/// 弹出系统通知（Windows 为 Toast，macOS 为通知中心，Linux 走 D-Bus）。
+///
+/// 各平台的实现都是同步调用，放到阻塞线程池中执行，避免卡住异步运行时。
+pub async fn show(summary: &str, body: &str) -> Result<(), String> {
+    let notification = notify_rust::Notification::new()
+        .appname("RightCode")
+        .summary(summary)
+        .body(body)
+        .finalize();
+    tokio::task::spawn_blocking(move || notification.show().map(drop))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LowQuotaAlerts;
+    use crate::api::Subscription;
+
+    fn thresholds_hit(alerts: &mut LowQuotaAlerts, remaining: f64) -> Vec<u32> {
+        alerts
+            .check(&[Subscription::fixture("Pro", remaining)], &[20, 10])
+            .into_iter()
+            .map(|alert| alert.threshold)
+            .collect()
+    }
+
+    #[test]
+    fn alerts_once_per_threshold() {
+        let mut alerts = LowQuotaAlerts::default();
+        assert!(thresholds_hit(&mut alerts, 50.0).is_empty());
+        assert_eq!(thresholds_hit(&mut alerts, 19.0), [20]);
+        assert!(thresholds_hit(&mut alerts, 15.0).is_empty());
+        assert!(thresholds_hit(&mut alerts, 19.5).is_empty());
+        assert_eq!(thresholds_hit(&mut alerts, 5.0), [10]);
+        assert!(thresholds_hit(&mut alerts, 1.0).is_empty());
+    }
+
+    #[test]
+    fn rearms_after_recovering_past_margin() {
+        let mut alerts = LowQuotaAlerts::default();
+        assert_eq!(thresholds_hit(&mut alerts, 9.0), [10]);
+        // 在阈值附近抖动不重复提醒
+        assert!(thresholds_hit(&mut alerts, 10.5).is_empty());
+        assert!(thresholds_hit(&mut alerts, 9.5).is_empty());
+        // 回升到 20% 档位内，再次跌破 10% 时重新提醒
+        assert!(thresholds_hit(&mut alerts, 15.0).is_empty());
+        assert_eq!(thresholds_hit(&mut alerts, 8.0), [10]);
+        // 充值回满后重新从 20% 开始提醒
+        assert!(thresholds_hit(&mut alerts, 100.0).is_empty());
+        assert_eq!(thresholds_hit(&mut alerts, 18.0), [20]);
+    }
+}
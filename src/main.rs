@@ -1,14 +1,85 @@
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
+mod about;
 mod api;
 mod app;
+mod audit;
 mod autostart;
+mod avoid;
 mod ball;
+mod bench;
+mod bus;
+mod capture;
+mod cli;
 mod config;
+mod display;
+mod doh;
+mod drag;
 mod executor;
+mod follow;
+mod history;
+mod hotkey;
+mod lock;
+mod low_quota;
+mod notifications;
+mod peek;
 mod platform;
+mod plugin;
+mod policy;
+mod provider;
+mod qr;
+mod relay;
+mod render_stats;
+mod report;
+mod schedule;
+mod screen;
+mod skin;
+mod sparkline;
+mod spend;
+mod spike;
+mod stats;
+mod text_fit;
+mod toast;
 mod tray;
+mod ui_state;
+mod vault;
+mod web;
+mod windows;
 
 fn main() -> iced::Result {
-    app::run()
+    let args = match cli::CliArgs::parse() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(frames) = args.bench_render {
+        println!("{}", bench::run(frames));
+        return Ok(());
+    }
+
+    if let Some(dir) = args.diagnostics.clone() {
+        match app::write_diagnostics(&args, &dir) {
+            Ok(path) => {
+                println!("{}", path.display());
+                return Ok(());
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(task) = args.task {
+        // 已有实例时转交后退出，否则启动后由新实例执行
+        if platform::forward_jump_task(windows::WindowRole::Ball.title(), task) {
+            return Ok(());
+        }
+        bus::send(bus::Command::JumpTask(task));
+    }
+
+    app::run(args)
 }
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use crate::api::Subscription;
+
+/// 最多保留的通知条数，超出后丢弃最早的。
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Error,
+    ReAuth,
+    Refill,
+    SoftCap,
+    Depleted,
+    Spike,
+    LowQuota,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 7] = [
+        NotificationKind::Error,
+        NotificationKind::ReAuth,
+        NotificationKind::Refill,
+        NotificationKind::SoftCap,
+        NotificationKind::Depleted,
+        NotificationKind::Spike,
+        NotificationKind::LowQuota,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationKind::Error => "错误",
+            NotificationKind::ReAuth => "验证",
+            NotificationKind::Refill => "充值",
+            NotificationKind::SoftCap => "超额",
+            NotificationKind::Depleted => "用完",
+            NotificationKind::Spike => "异常",
+            NotificationKind::LowQuota => "额度低",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub at: SystemTime,
+    pub kind: NotificationKind,
+    pub message: String,
+}
+
+/// 本次运行期间发出的通知，系统通知中心可能吞掉提示，这里留底备查。
+#[derive(Debug, Clone, Default)]
+pub struct NotificationLog {
+    entries: VecDeque<Notification>,
+}
+
+impl NotificationLog {
+    pub fn push(&mut self, kind: NotificationKind, message: impl Into<String>) {
+        self.push_at(SystemTime::now(), kind, message.into());
+    }
+
+    fn push_at(&mut self, at: SystemTime, kind: NotificationKind, message: String) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Notification { at, kind, message });
+    }
+
+    /// 按时间倒序列出，`filter` 为 `None` 时列出全部。
+    pub fn entries(
+        &self,
+        filter: Option<NotificationKind>,
+    ) -> impl Iterator<Item = &Notification> + '_ {
+        self.entries
+            .iter()
+            .rev()
+            .filter(move |entry| filter.is_none_or(|kind| entry.kind == kind))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// 剩余额度较上次增加的订阅（充值或额度重置），返回名称与增加量。
+pub fn detect_refills(previous: &[Subscription], current: &[Subscription]) -> Vec<(String, f64)> {
+    current
+        .iter()
+        .filter_map(|sub| {
+            let before = previous.iter().find(|p| p.name == sub.name)?;
+            let added = sub.remaining_quota - before.remaining_quota;
+            (added > 0.0).then(|| (sub.name.clone(), added))
+        })
+        .collect()
+}
+
+/// 剩余额度由正数降到 0 的订阅；首次拉取时没有对比对象，不算用完。
+pub fn detect_depletions(previous: &[Subscription], current: &[Subscription]) -> Vec<String> {
+    current
+        .iter()
+        .filter(|sub| sub.remaining_quota <= 0.0)
+        .filter(|sub| {
+            previous
+                .iter()
+                .any(|p| p.name == sub.name && p.remaining_quota > 0.0)
+        })
+        .map(|sub| sub.name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{
+        MAX_ENTRIES, NotificationKind, NotificationLog, detect_depletions, detect_refills,
+    };
+    use crate::api::Subscription;
+
+    #[test]
+    fn lists_newest_first_with_filter() {
+        let mut log = NotificationLog::default();
+        let start = SystemTime::UNIX_EPOCH;
+        log.push_at(start, NotificationKind::Error, "timeout".into());
+        log.push_at(
+            start + Duration::from_secs(1),
+            NotificationKind::Refill,
+            "plan +10".into(),
+        );
+        log.push_at(
+            start + Duration::from_secs(2),
+            NotificationKind::Error,
+            "502".into(),
+        );
+
+        let errors: Vec<&str> = log
+            .entries(Some(NotificationKind::Error))
+            .map(|n| n.message.as_str())
+            .collect();
+        assert_eq!(errors, ["502", "timeout"]);
+        assert_eq!(log.entries(None).count(), 3);
+    }
+
+    #[test]
+    fn drops_oldest_entries_beyond_capacity() {
+        let mut log = NotificationLog::default();
+        for i in 0..MAX_ENTRIES + 5 {
+            log.push(NotificationKind::Error, i.to_string());
+        }
+        assert_eq!(log.entries(None).count(), MAX_ENTRIES);
+        assert_eq!(log.entries(None).last().unwrap().message, "5");
+    }
+
+    #[test]
+    fn detects_refills_only_for_known_subscriptions() {
+        let previous = [
+            Subscription::fixture("a", 10.0),
+            Subscription::fixture("b", 50.0),
+        ];
+        let current = [
+            Subscription::fixture("a", 60.0),
+            Subscription::fixture("b", 40.0),
+            Subscription::fixture("c", 100.0),
+        ];
+        assert_eq!(
+            detect_refills(&previous, &current),
+            vec![("a".to_string(), 50.0)]
+        );
+    }
+
+    #[test]
+    fn detects_depletion_once_per_transition() {
+        let previous = [
+            Subscription::fixture("a", 0.5),
+            Subscription::fixture("b", 0.0),
+        ];
+        let current = [
+            Subscription::fixture("a", 0.0),
+            Subscription::fixture("b", 0.0),
+            Subscription::fixture("c", 0.0),
+        ];
+        assert_eq!(detect_depletions(&previous, &current), ["a"]);
+        assert!(detect_depletions(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use iced::{Point, Rectangle};
+
+/// 光标离开后悬浮球继续显示的时长。
+const PEEK_DURATION: Duration = Duration::from_secs(2);
+/// 淡入时长。
+const FADE_IN: Duration = Duration::from_millis(300);
+/// 光标进入悬浮球原位置外扩该距离（逻辑像素）内即触发。
+const PEEK_MARGIN: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeekChange {
+    Show,
+    Hide,
+}
+
+/// 悬浮球隐藏后，光标靠近它原来所在的位置时临时淡入显示。
+#[derive(Debug, Clone, Copy)]
+pub struct Peek {
+    area: Rectangle,
+    /// 正在显示时为 (开始时间, 预计隐藏时间)。
+    showing: Option<(Instant, Instant)>,
+}
+
+impl Peek {
+    /// `area` 为隐藏前悬浮球窗口的位置与大小。
+    pub fn new(area: Rectangle) -> Self {
+        Self {
+            area: area.expand(PEEK_MARGIN),
+            showing: None,
+        }
+    }
+
+    pub fn is_showing(&self) -> bool {
+        self.showing.is_some()
+    }
+
+    /// 按光标位置推进状态；光标停在附近时持续显示，离开 2 秒后隐藏。
+    pub fn update(&mut self, cursor: Option<Point>, now: Instant) -> Option<PeekChange> {
+        let near = cursor.is_some_and(|cursor| self.area.contains(cursor));
+        match self.showing {
+            None if near => {
+                self.showing = Some((now, now + PEEK_DURATION));
+                Some(PeekChange::Show)
+            }
+            None => None,
+            Some((since, _)) if near => {
+                self.showing = Some((since, now + PEEK_DURATION));
+                None
+            }
+            Some((_, until)) if now >= until => {
+                self.showing = None;
+                Some(PeekChange::Hide)
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// 淡入进度（0-1），未显示时为 0。
+    pub fn opacity(&self, now: Instant) -> f32 {
+        match self.showing {
+            Some((since, _)) => (now.saturating_duration_since(since).as_secs_f32()
+                / FADE_IN.as_secs_f32())
+            .min(1.0),
+            None => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use iced::{Point, Rectangle, Size};
+
+    use super::{Peek, PeekChange};
+
+    #[test]
+    fn shows_while_hovering_and_hides_two_seconds_after_leaving() {
+        let mut peek = Peek::new(Rectangle::new(
+            Point::new(1800.0, 900.0),
+            Size::new(120.0, 120.0),
+        ));
+        let start = Instant::now();
+        let near = Some(Point::new(1790.0, 950.0));
+        let away = Some(Point::new(400.0, 400.0));
+
+        assert_eq!(peek.update(away, start), None);
+        assert_eq!(peek.update(near, start), Some(PeekChange::Show));
+        assert_eq!(peek.opacity(start), 0.0);
+        assert!((peek.opacity(start + Duration::from_millis(150)) - 0.5).abs() < 1e-3);
+
+        let left = start + Duration::from_secs(5);
+        assert_eq!(peek.update(near, left), None);
+        assert_eq!(peek.update(away, left + Duration::from_secs(1)), None);
+        assert!(peek.is_showing());
+        assert_eq!(
+            peek.update(away, left + Duration::from_secs(2)),
+            Some(PeekChange::Hide)
+        );
+        assert!(!peek.is_showing());
+        assert_eq!(peek.update(None, left + Duration::from_secs(3)), None);
+    }
+}
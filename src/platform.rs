@@ -1,15 +1,80 @@
+use std::time::Duration;
+
 use iced::window::raw_window_handle::WindowHandle;
+use iced::{Point, Rectangle};
 
 #[cfg(windows)]
 use iced::window::raw_window_handle::RawWindowHandle;
 
 #[cfg(windows)]
 use windows_sys::Win32::{
-    Foundation::{HWND, RECT},
-    Graphics::Gdi::{CreateEllipticRgn, DeleteObject, SetWindowRgn},
-    UI::WindowsAndMessaging::GetClientRect,
+    Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+    Graphics::Gdi::{
+        CreateEllipticRgn, DeleteObject, EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR,
+        MONITORINFO, MONITORINFOF_PRIMARY, SetWindowRgn,
+    },
+    System::SystemInformation::GetTickCount,
+    UI::Input::KeyboardAndMouse::{GetDoubleClickTime, GetLastInputInfo, LASTINPUTINFO},
+    UI::Shell::{DefSubclassProc, SetWindowSubclass},
+    UI::WindowsAndMessaging::{
+        FindWindowW, GetClientRect, GetCursorPos, GetForegroundWindow, GetWindowRect, PostMessageW,
+        RegisterWindowMessageW, SPI_GETWORKAREA, SPI_SETWORKAREA, SystemParametersInfoW,
+        WM_DISPLAYCHANGE, WM_DPICHANGED, WM_SETTINGCHANGE,
+    },
 };
 
+/// 任务栏跳转列表与进度条仅 Windows 支持。
+pub const TASKBAR_SUPPORTED: bool = cfg!(windows);
+
+/// 跳转列表中的任务：以 `--task <名称>` 启动新进程，再转交给已运行的实例。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpTask {
+    Refresh,
+    Settings,
+    /// 暂停或恢复自动刷新。
+    Pause,
+}
+
+impl JumpTask {
+    pub const ALL: [JumpTask; 3] = [JumpTask::Refresh, JumpTask::Settings, JumpTask::Pause];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            JumpTask::Refresh => "refresh",
+            JumpTask::Settings => "settings",
+            JumpTask::Pause => "pause",
+        }
+    }
+
+    pub fn parse_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|task| task.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// 自动刷新已暂停时「暂停」任务显示为「恢复」。
+    #[cfg(windows)]
+    fn label(self, paused: bool) -> &'static str {
+        match self {
+            JumpTask::Refresh => "刷新",
+            JumpTask::Settings => "设置",
+            JumpTask::Pause if paused => "恢复",
+            JumpTask::Pause => "暂停",
+        }
+    }
+}
+
+/// 任务栏按钮上的进度条。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskbarProgress {
+    /// 不显示（未开启、没有数据或隐私遮挡）。
+    Hidden,
+    /// 剩余百分比。
+    Normal(u8),
+    /// 自动刷新暂停时显示为黄色。
+    Paused(u8),
+}
+
 pub fn set_round_window_region(handle: WindowHandle<'_>, round: bool) {
     #[cfg(windows)]
     set_round_window_region_windows(handle, round);
@@ -62,3 +127,1013 @@ fn set_round_window_region_windows(handle: WindowHandle<'_>, round: bool) {
         }
     }
 }
+
+#[cfg(windows)]
+const DISPLAY_SUBCLASS_ID: usize = 1;
+
+/// 监听分辨率、DPI 与工作区（任务栏）变化；仅 Windows 支持，其他平台靠定时比较显示器列表。
+pub fn watch_display_changes(handle: WindowHandle<'_>) {
+    #[cfg(windows)]
+    {
+        let RawWindowHandle::Win32(win32) = handle.as_raw() else {
+            return;
+        };
+        let hwnd = win32.hwnd.get() as HWND;
+        unsafe {
+            SetWindowSubclass(hwnd, Some(display_subclass), DISPLAY_SUBCLASS_ID, 0);
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = handle;
+    }
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn display_subclass(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    _data: usize,
+) -> LRESULT {
+    // 同一子类顺带接收其他进程转交的跳转列表任务
+    if message == jump_task_message() {
+        if let Some(task) = JumpTask::ALL.get(wparam).copied() {
+            crate::bus::send(crate::bus::Command::JumpTask(task));
+        }
+        return 0;
+    }
+
+    let changed = match message {
+        WM_DISPLAYCHANGE | WM_DPICHANGED => true,
+        WM_SETTINGCHANGE => wparam == SPI_SETWORKAREA as WPARAM,
+        _ => false,
+    };
+    if changed {
+        crate::bus::send(crate::bus::Command::DisplayChanged);
+    }
+    unsafe { DefSubclassProc(hwnd, message, wparam, lparam) }
+}
+
+#[cfg(windows)]
+fn jump_task_message() -> u32 {
+    static MESSAGE: std::sync::OnceLock<u32> = std::sync::OnceLock::new();
+    *MESSAGE.get_or_init(|| {
+        let name: Vec<u16> = "RightCode.FloatingBall.JumpTask"
+            .encode_utf16()
+            .chain([0])
+            .collect();
+        unsafe { RegisterWindowMessageW(name.as_ptr()) }
+    })
+}
+
+/// 把跳转列表任务转交给标题为 `window_title` 的已运行实例；找不到窗口时返回 false。
+pub fn forward_jump_task(window_title: &str, task: JumpTask) -> bool {
+    #[cfg(windows)]
+    {
+        let title: Vec<u16> = window_title.encode_utf16().chain([0]).collect();
+        let index = JumpTask::ALL.iter().position(|t| *t == task).unwrap_or(0);
+        unsafe {
+            let hwnd = FindWindowW(std::ptr::null(), title.as_ptr());
+            !hwnd.is_null() && PostMessageW(hwnd, jump_task_message(), index, 0) != 0
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (window_title, task);
+        false
+    }
+}
+
+/// 记住悬浮球窗口，任务栏进度条画在它的按钮上。
+pub fn attach_taskbar(handle: WindowHandle<'_>) {
+    #[cfg(windows)]
+    {
+        let RawWindowHandle::Win32(win32) = handle.as_raw() else {
+            return;
+        };
+        taskbar::attach(win32.hwnd.get());
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = handle;
+    }
+}
+
+/// 更新任务栏进度条；与上次相同时不重复调用。
+pub fn set_taskbar_progress(progress: TaskbarProgress) {
+    #[cfg(windows)]
+    taskbar::set_progress(progress);
+
+    #[cfg(not(windows))]
+    {
+        let _ = progress;
+    }
+}
+
+/// 写入（或清除）跳转列表中的任务；`paused` 决定暂停任务显示为「暂停」还是「恢复」。
+pub fn set_jump_list(enabled: bool, paused: bool) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        taskbar::set_jump_list(enabled, paused)
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (enabled, paused);
+        Ok(())
+    }
+}
+
+/// Windows「辅助功能 - 文本大小」的缩放比例（1.0-2.25），其他平台为 1.0。
+pub fn text_scale() -> f32 {
+    #[cfg(windows)]
+    {
+        windows_text_scale().map_or(1.0, |percent| (percent as f32 / 100.0).clamp(1.0, 2.25))
+    }
+
+    #[cfg(not(windows))]
+    {
+        1.0
+    }
+}
+
+#[cfg(windows)]
+fn windows_text_scale() -> Option<u32> {
+    read_user_dword("SOFTWARE\\Microsoft\\Accessibility", "TextScaleFactor")
+}
+
+/// 任务栏（Windows）或菜单栏（macOS）是否为浅色；其他平台返回 `false`。
+pub fn light_taskbar() -> bool {
+    #[cfg(windows)]
+    {
+        read_user_dword(
+            "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+            "SystemUsesLightTheme",
+        )
+        .is_some_and(|light| light != 0)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::light_menu_bar()
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// 读取 `HKEY_CURRENT_USER` 下的 DWORD 值。
+#[cfg(windows)]
+fn read_user_dword(subkey: &str, value: &str) -> Option<u32> {
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Registry::{HKEY_CURRENT_USER, RRF_RT_REG_DWORD, RegGetValueW};
+
+    let subkey: Vec<u16> = subkey.encode_utf16().chain([0]).collect();
+    let value: Vec<u16> = value.encode_utf16().chain([0]).collect();
+    let mut data: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            (&mut data as *mut u32).cast(),
+            &mut size,
+        )
+    };
+    (status == ERROR_SUCCESS).then_some(data)
+}
+
+/// 用系统默认浏览器打开链接。
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    // 直接交给 ShellExecuteW，不经过 cmd，链接里的 `&`、`^` 等字符不会被当作命令解析。
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::UI::Shell::ShellExecuteW;
+        use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+        let verb: Vec<u16> = "open".encode_utf16().chain([0]).collect();
+        let target: Vec<u16> = url.encode_utf16().chain([0]).collect();
+        let result = unsafe {
+            ShellExecuteW(
+                std::ptr::null_mut(),
+                verb.as_ptr(),
+                target.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                SW_SHOWNORMAL,
+            )
+        };
+        // 返回值不大于 32 表示失败，此时它本身就是错误码。
+        let code = result as isize;
+        if code > 32 {
+            Ok(())
+        } else {
+            Err(std::io::Error::other(format!(
+                "ShellExecuteW 失败（错误码 {code}）"
+            )))
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    #[cfg(not(windows))]
+    command.arg(url).spawn().map(|_| ())
+}
+
+/// 通过系统 shell 运行用户配置的命令，不等待结束；`envs` 附加到命令的环境变量中。
+pub fn run_command(command: &str, envs: &[(&str, &str)]) -> std::io::Result<()> {
+    #[cfg(windows)]
+    let mut process = {
+        let mut process = std::process::Command::new("cmd");
+        process.arg("/C");
+        process
+    };
+
+    #[cfg(not(windows))]
+    let mut process = {
+        let mut process = std::process::Command::new("sh");
+        process.arg("-c");
+        process
+    };
+
+    process
+        .arg(command)
+        .envs(envs.iter().copied())
+        .spawn()
+        .map(|_| ())
+}
+
+/// 全局光标位置（逻辑像素）；`scale_factor` 为窗口缩放比例，仅 Windows 需要换算。
+/// 其他平台暂不支持，返回 `None`。
+pub fn cursor_position(scale_factor: f32) -> Option<Point> {
+    #[cfg(windows)]
+    {
+        let mut point = POINT { x: 0, y: 0 };
+        if unsafe { GetCursorPos(&mut point) } == 0 {
+            return None;
+        }
+        let scale = if scale_factor > 0.0 {
+            scale_factor
+        } else {
+            1.0
+        };
+        Some(Point::new(point.x as f32 / scale, point.y as f32 / scale))
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = scale_factor;
+        macos::cursor_position()
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let _ = scale_factor;
+        None
+    }
+}
+
+/// 系统设置的双击间隔；其他平台取常见的 500 毫秒。
+pub fn double_click_time() -> Duration {
+    #[cfg(windows)]
+    {
+        Duration::from_millis(u64::from(unsafe { GetDoubleClickTime() }))
+    }
+
+    #[cfg(not(windows))]
+    {
+        Duration::from_millis(500)
+    }
+}
+
+/// 距离用户上次键盘或鼠标输入的时长；不支持的平台返回 `None`。
+pub fn idle_duration() -> Option<Duration> {
+    #[cfg(windows)]
+    {
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if unsafe { GetLastInputInfo(&mut info) } == 0 {
+            return None;
+        }
+        // 两者都是开机以来的毫秒数，约 49.7 天回绕一次
+        let elapsed = unsafe { GetTickCount() }.wrapping_sub(info.dwTime);
+        Some(Duration::from_millis(u64::from(elapsed)))
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::idle_duration()
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        None
+    }
+}
+
+/// 当前前台窗口的矩形（逻辑像素），用于智能避让；仅 Windows 支持。
+pub fn foreground_window_rect(scale_factor: f32) -> Option<Rectangle> {
+    #[cfg(windows)]
+    {
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        };
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.is_null() || GetWindowRect(hwnd, &mut rect) == 0 {
+                return None;
+            }
+        }
+        Some(logical_rect(rect, scale_factor))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = scale_factor;
+        None
+    }
+}
+
+/// 主屏幕去掉任务栏后的工作区（逻辑像素）；仅 Windows 支持。
+pub fn work_area(scale_factor: f32) -> Option<Rectangle> {
+    #[cfg(windows)]
+    {
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        };
+        let ok = unsafe {
+            SystemParametersInfoW(SPI_GETWORKAREA, 0, (&mut rect as *mut RECT).cast(), 0)
+        };
+        (ok != 0).then(|| logical_rect(rect, scale_factor))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = scale_factor;
+        None
+    }
+}
+
+/// 所有显示器的工作区（逻辑像素），主显示器排在最前；不支持的平台返回空列表。
+/// macOS 上为整块屏幕的范围，不扣除菜单栏与程序坞。
+pub fn monitor_work_areas(scale_factor: f32) -> Vec<Rectangle> {
+    #[cfg(windows)]
+    {
+        unsafe extern "system" fn collect(
+            monitor: HMONITOR,
+            _hdc: HDC,
+            _clip: *mut RECT,
+            data: LPARAM,
+        ) -> windows_sys::core::BOOL {
+            let monitors = unsafe { &mut *(data as *mut Vec<(bool, RECT)>) };
+            let mut info: MONITORINFO = unsafe { std::mem::zeroed() };
+            info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if unsafe { GetMonitorInfoW(monitor, &mut info) } != 0 {
+                monitors.push((info.dwFlags & MONITORINFOF_PRIMARY != 0, info.rcWork));
+            }
+            1
+        }
+
+        let mut monitors: Vec<(bool, RECT)> = Vec::new();
+        unsafe {
+            EnumDisplayMonitors(
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                Some(collect),
+                &mut monitors as *mut Vec<(bool, RECT)> as LPARAM,
+            );
+        }
+        monitors.sort_by_key(|(primary, _)| !primary);
+        monitors
+            .into_iter()
+            .map(|(_, rect)| logical_rect(rect, scale_factor))
+            .collect()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = scale_factor;
+        macos::display_bounds()
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let _ = scale_factor;
+        Vec::new()
+    }
+}
+
+/// 屏幕是否正在被捕获或共享（会议软件、录屏）；不支持的平台返回 `false`。
+/// Windows 读取「屏幕捕获」权限的使用记录（有开始、无结束即正在使用），
+/// macOS 读取会话的屏幕共享状态。
+pub fn is_screen_shared() -> bool {
+    #[cfg(windows)]
+    {
+        [
+            "graphicsCaptureProgrammatic",
+            "graphicsCaptureWithoutBorder",
+        ]
+        .iter()
+        .any(|capability| windows_capability_in_use(capability))
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::screen_is_shared()
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// `ConsentStore\<权限>` 下每个应用一个子键，桌面程序位于 `NonPackaged` 下一层。
+#[cfg(windows)]
+fn windows_capability_in_use(capability: &str) -> bool {
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Registry::{
+        HKEY, HKEY_CURRENT_USER, KEY_READ, RRF_RT_REG_QWORD, RegCloseKey, RegEnumKeyExW,
+        RegGetValueW, RegOpenKeyExW,
+    };
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn read_qword(key: HKEY, name: &str) -> Option<u64> {
+        let name = wide(name);
+        let mut value: u64 = 0;
+        let mut size = std::mem::size_of::<u64>() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                key,
+                std::ptr::null(),
+                name.as_ptr(),
+                RRF_RT_REG_QWORD,
+                std::ptr::null_mut(),
+                (&mut value as *mut u64).cast(),
+                &mut size,
+            )
+        };
+        (status == ERROR_SUCCESS).then_some(value)
+    }
+
+    fn any_in_use(parent: HKEY, path: &str, depth: u32) -> bool {
+        let mut key: HKEY = std::ptr::null_mut();
+        let path = wide(path);
+        if unsafe { RegOpenKeyExW(parent, path.as_ptr(), 0, KEY_READ, &mut key) } != ERROR_SUCCESS {
+            return false;
+        }
+
+        let started = read_qword(key, "LastUsedTimeStart").is_some_and(|start| start != 0);
+        let mut in_use = started && read_qword(key, "LastUsedTimeStop") == Some(0);
+
+        let mut index = 0;
+        while !in_use && depth > 0 {
+            let mut name = [0u16; 512];
+            let mut len = name.len() as u32;
+            let status = unsafe {
+                RegEnumKeyExW(
+                    key,
+                    index,
+                    name.as_mut_ptr(),
+                    &mut len,
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                )
+            };
+            if status != ERROR_SUCCESS {
+                break;
+            }
+            in_use = any_in_use(
+                key,
+                &String::from_utf16_lossy(&name[..len as usize]),
+                depth - 1,
+            );
+            index += 1;
+        }
+
+        unsafe { RegCloseKey(key) };
+        in_use
+    }
+
+    any_in_use(
+        HKEY_CURRENT_USER,
+        &format!(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore\\{capability}"
+        ),
+        2,
+    )
+}
+
+#[cfg(windows)]
+fn logical_rect(rect: RECT, scale_factor: f32) -> Rectangle {
+    let scale = if scale_factor > 0.0 {
+        scale_factor
+    } else {
+        1.0
+    };
+    Rectangle {
+        x: rect.left as f32 / scale,
+        y: rect.top as f32 / scale,
+        width: (rect.right - rect.left) as f32 / scale,
+        height: (rect.bottom - rect.top) as f32 / scale,
+    }
+}
+
+/// 任务栏按钮的 COM 接口（ITaskbarList3、ICustomDestinationList），只在主线程上调用。
+///
+/// windows-sys 不包含 COM 接口，这里只声明用到的虚表槽位，其余以占位补齐。
+#[cfg(windows)]
+mod taskbar {
+    use std::cell::{Cell, OnceCell};
+    use std::ffi::c_void;
+    use std::marker::PhantomData;
+    use std::os::windows::ffi::OsStrExt;
+    use std::sync::atomic::{AtomicIsize, Ordering};
+
+    use windows_sys::Win32::Foundation::{HWND, PROPERTYKEY};
+    use windows_sys::Win32::System::Com::{
+        CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx,
+    };
+    use windows_sys::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ShellLink, TBPF_NOPROGRESS, TBPF_NORMAL,
+        TBPF_PAUSED, TBPFLAG, TaskbarList,
+    };
+    use windows_sys::core::{GUID, HRESULT, PCWSTR};
+
+    use super::{JumpTask, TaskbarProgress};
+
+    /// 跳转列表项的标题（System.Title）。
+    const PKEY_TITLE: PROPERTYKEY = PROPERTYKEY {
+        fmtid: GUID::from_u128(0xf29f85e0_4ff9_1068_ab91_08002b27b3d9),
+        pid: 2,
+    };
+
+    const IID_ITASKBARLIST3: GUID = GUID::from_u128(0xea1afb91_9e28_4b86_90e9_9e9f8a5eefaf);
+    const IID_ICUSTOMDESTINATIONLIST: GUID =
+        GUID::from_u128(0x6332debf_87b5_4670_90c0_5e57b408a49e);
+    const IID_IOBJECTARRAY: GUID = GUID::from_u128(0x92ca9dcd_5622_4bba_a805_5e9f541bd8c9);
+    const IID_IOBJECTCOLLECTION: GUID = GUID::from_u128(0x5632b1a4_e38a_400a_928a_d4cd63230295);
+    const IID_ISHELLLINKW: GUID = GUID::from_u128(0x000214f9_0000_0000_c000_000000000046);
+    const IID_IPROPERTYSTORE: GUID = GUID::from_u128(0x886d8eeb_8cf2_4446_8d02_cdba1dbdcf99);
+
+    const VT_LPWSTR: u16 = 31;
+
+    type Slot = usize;
+
+    #[repr(C)]
+    struct IUnknownVtbl {
+        query_interface:
+            unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+        _add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+        release: unsafe extern "system" fn(*mut c_void) -> u32,
+    }
+
+    #[repr(C)]
+    struct ITaskbarList3Vtbl {
+        _base: IUnknownVtbl,
+        hr_init: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+        // AddTab、DeleteTab、ActivateTab、SetActiveAlt、MarkFullscreenWindow
+        _unused: [Slot; 5],
+        set_progress_value: unsafe extern "system" fn(*mut c_void, HWND, u64, u64) -> HRESULT,
+        set_progress_state: unsafe extern "system" fn(*mut c_void, HWND, TBPFLAG) -> HRESULT,
+    }
+
+    #[repr(C)]
+    struct ICustomDestinationListVtbl {
+        _base: IUnknownVtbl,
+        _set_app_id: Slot,
+        begin_list: unsafe extern "system" fn(
+            *mut c_void,
+            *mut u32,
+            *const GUID,
+            *mut *mut c_void,
+        ) -> HRESULT,
+        // AppendCategory、AppendKnownCategory
+        _unused: [Slot; 2],
+        add_user_tasks: unsafe extern "system" fn(*mut c_void, *mut c_void) -> HRESULT,
+        commit_list: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+        _get_removed_destinations: Slot,
+        delete_list: unsafe extern "system" fn(*mut c_void, PCWSTR) -> HRESULT,
+    }
+
+    #[repr(C)]
+    struct IObjectArrayVtbl {
+        _base: IUnknownVtbl,
+    }
+
+    #[repr(C)]
+    struct IObjectCollectionVtbl {
+        _base: IUnknownVtbl,
+        // IObjectArray::GetCount、GetAt
+        _unused: [Slot; 2],
+        add_object: unsafe extern "system" fn(*mut c_void, *mut c_void) -> HRESULT,
+    }
+
+    #[repr(C)]
+    struct IShellLinkWVtbl {
+        _base: IUnknownVtbl,
+        // GetPath ... GetArguments
+        _unused: [Slot; 8],
+        set_arguments: unsafe extern "system" fn(*mut c_void, PCWSTR) -> HRESULT,
+        // GetHotkey ... GetIconLocation
+        _unused2: [Slot; 5],
+        set_icon_location: unsafe extern "system" fn(*mut c_void, PCWSTR, i32) -> HRESULT,
+        // SetRelativePath、Resolve
+        _unused3: [Slot; 2],
+        set_path: unsafe extern "system" fn(*mut c_void, PCWSTR) -> HRESULT,
+    }
+
+    #[repr(C)]
+    struct IPropertyStoreVtbl {
+        _base: IUnknownVtbl,
+        // GetCount、GetAt、GetValue
+        _unused: [Slot; 3],
+        set_value: unsafe extern "system" fn(
+            *mut c_void,
+            *const PROPERTYKEY,
+            *const PropVariant,
+        ) -> HRESULT,
+        commit: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+    }
+
+    /// 只用到 `VT_LPWSTR` 的 PROPVARIANT，大小与系统定义一致；字段由系统读取。
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct PropVariant {
+        vt: u16,
+        _reserved: [u16; 3],
+        value: PCWSTR,
+        _padding: usize,
+    }
+
+    /// 持有一个接口指针，离开作用域时 Release。
+    struct Com<V> {
+        ptr: *mut c_void,
+        _vtbl: PhantomData<V>,
+    }
+
+    impl<V> Com<V> {
+        fn create(clsid: &GUID, iid: &GUID) -> Result<Self, HRESULT> {
+            let mut ptr = std::ptr::null_mut();
+            check(unsafe {
+                CoCreateInstance(
+                    clsid,
+                    std::ptr::null_mut(),
+                    CLSCTX_INPROC_SERVER,
+                    iid,
+                    &mut ptr,
+                )
+            })?;
+            Ok(Self::from_raw(ptr))
+        }
+
+        fn from_raw(ptr: *mut c_void) -> Self {
+            Self {
+                ptr,
+                _vtbl: PhantomData,
+            }
+        }
+
+        /// 所有虚表都以 IUnknown 开头。
+        fn unknown(&self) -> &IUnknownVtbl {
+            unsafe { &**(self.ptr as *const *const IUnknownVtbl) }
+        }
+
+        fn vtbl(&self) -> &V {
+            unsafe { &**(self.ptr as *const *const V) }
+        }
+
+        fn cast<U>(&self, iid: &GUID) -> Result<Com<U>, HRESULT> {
+            let mut ptr = std::ptr::null_mut();
+            check(unsafe { (self.unknown().query_interface)(self.ptr, iid, &mut ptr) })?;
+            Ok(Com::from_raw(ptr))
+        }
+    }
+
+    impl<V> Drop for Com<V> {
+        fn drop(&mut self) {
+            unsafe { (self.unknown().release)(self.ptr) };
+        }
+    }
+
+    fn check(hr: HRESULT) -> Result<(), HRESULT> {
+        if hr < 0 { Err(hr) } else { Ok(()) }
+    }
+
+    fn wide(text: impl AsRef<std::ffi::OsStr>) -> Vec<u16> {
+        text.as_ref().encode_wide().chain([0]).collect()
+    }
+
+    static WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+    thread_local! {
+        static TASKBAR: OnceCell<Option<Com<ITaskbarList3Vtbl>>> = const { OnceCell::new() };
+        static SHOWN: Cell<Option<TaskbarProgress>> = const { Cell::new(None) };
+    }
+
+    fn init_com() {
+        // winit 已为拖放初始化过时返回 S_FALSE，可以忽略
+        let _ = unsafe { CoInitializeEx(std::ptr::null(), COINIT_APARTMENTTHREADED as u32) };
+    }
+
+    pub fn attach(hwnd: isize) {
+        WINDOW.store(hwnd, Ordering::Relaxed);
+        SHOWN.set(None);
+    }
+
+    pub fn set_progress(progress: TaskbarProgress) {
+        let hwnd = WINDOW.load(Ordering::Relaxed);
+        if hwnd == 0 || SHOWN.get() == Some(progress) {
+            return;
+        }
+
+        TASKBAR.with(|taskbar| {
+            let taskbar = taskbar.get_or_init(|| {
+                init_com();
+                let taskbar =
+                    Com::<ITaskbarList3Vtbl>::create(&TaskbarList, &IID_ITASKBARLIST3).ok()?;
+                check(unsafe { (taskbar.vtbl().hr_init)(taskbar.ptr) }).ok()?;
+                Some(taskbar)
+            });
+            let Some(taskbar) = taskbar else {
+                return;
+            };
+
+            let hwnd = hwnd as HWND;
+            let vtbl = taskbar.vtbl();
+            let result = unsafe {
+                match progress {
+                    TaskbarProgress::Hidden => check((vtbl.set_progress_state)(
+                        taskbar.ptr,
+                        hwnd,
+                        TBPF_NOPROGRESS,
+                    )),
+                    TaskbarProgress::Normal(percent) | TaskbarProgress::Paused(percent) => {
+                        let state = if matches!(progress, TaskbarProgress::Paused(_)) {
+                            TBPF_PAUSED
+                        } else {
+                            TBPF_NORMAL
+                        };
+                        check((vtbl.set_progress_state)(taskbar.ptr, hwnd, state)).and_then(|()| {
+                            check((vtbl.set_progress_value)(
+                                taskbar.ptr,
+                                hwnd,
+                                percent.into(),
+                                100,
+                            ))
+                        })
+                    }
+                }
+            };
+            // 任务栏按钮尚未创建时会失败，下次同步再试
+            if result.is_ok() {
+                SHOWN.set(Some(progress));
+            }
+        });
+    }
+
+    pub fn set_jump_list(enabled: bool, paused: bool) -> Result<(), String> {
+        init_com();
+        let exe = std::env::current_exe().map_err(|err| err.to_string())?;
+        write_jump_list(enabled, paused, &wide(&exe))
+            .map_err(|hr| format!("HRESULT 0x{:08X}", hr as u32))
+    }
+
+    fn write_jump_list(enabled: bool, paused: bool, exe: &[u16]) -> Result<(), HRESULT> {
+        let list = Com::<ICustomDestinationListVtbl>::create(
+            &DestinationList,
+            &IID_ICUSTOMDESTINATIONLIST,
+        )?;
+        let vtbl = list.vtbl();
+        unsafe {
+            if !enabled {
+                return check((vtbl.delete_list)(list.ptr, std::ptr::null()));
+            }
+
+            let mut slots = 0;
+            let mut removed = std::ptr::null_mut();
+            check((vtbl.begin_list)(
+                list.ptr,
+                &mut slots,
+                &IID_IOBJECTARRAY,
+                &mut removed,
+            ))?;
+            drop(Com::<IObjectArrayVtbl>::from_raw(removed));
+
+            let tasks = Com::<IObjectCollectionVtbl>::create(
+                &EnumerableObjectCollection,
+                &IID_IOBJECTCOLLECTION,
+            )?;
+            for task in JumpTask::ALL {
+                let link = Com::<IShellLinkWVtbl>::create(&ShellLink, &IID_ISHELLLINKW)?;
+                let arguments = wide(format!("--task {}", task.name()));
+                check((link.vtbl().set_path)(link.ptr, exe.as_ptr()))?;
+                check((link.vtbl().set_arguments)(link.ptr, arguments.as_ptr()))?;
+                check((link.vtbl().set_icon_location)(link.ptr, exe.as_ptr(), 0))?;
+
+                let store = link.cast::<IPropertyStoreVtbl>(&IID_IPROPERTYSTORE)?;
+                let title = wide(task.label(paused));
+                let value = PropVariant {
+                    vt: VT_LPWSTR,
+                    _reserved: [0; 3],
+                    value: title.as_ptr(),
+                    _padding: 0,
+                };
+                check((store.vtbl().set_value)(store.ptr, &PKEY_TITLE, &value))?;
+                check((store.vtbl().commit)(store.ptr))?;
+                check((tasks.vtbl().add_object)(tasks.ptr, link.ptr))?;
+            }
+            let array = tasks.cast::<IObjectArrayVtbl>(&IID_IOBJECTARRAY)?;
+            check((vtbl.add_user_tasks)(list.ptr, array.ptr))?;
+            check((vtbl.commit_list)(list.ptr))
+        }
+    }
+}
+
+/// CoreGraphics 的坐标以主屏左上角为原点、单位为点，与窗口的逻辑坐标一致。
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::c_void;
+    use std::time::Duration;
+
+    use iced::{Point, Rectangle, Size};
+
+    const COMBINED_SESSION_STATE: i32 = 0;
+    const UTF8_ENCODING: u32 = 0x0800_0100;
+    const ANY_INPUT_EVENT_TYPE: u32 = u32::MAX;
+
+    #[repr(C)]
+    struct CGPoint {
+        x: f64,
+        y: f64,
+    }
+
+    #[repr(C)]
+    struct CGSize {
+        width: f64,
+        height: f64,
+    }
+
+    #[repr(C)]
+    struct CGRect {
+        origin: CGPoint,
+        size: CGSize,
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    unsafe extern "C" {
+        fn CGEventCreate(source: *const c_void) -> *const c_void;
+        fn CGEventGetLocation(event: *const c_void) -> CGPoint;
+        fn CGEventSourceSecondsSinceLastEventType(state: i32, event_type: u32) -> f64;
+        fn CGGetActiveDisplayList(
+            max_displays: u32,
+            active_displays: *mut u32,
+            display_count: *mut u32,
+        ) -> i32;
+        fn CGDisplayBounds(display: u32) -> CGRect;
+        fn CGMainDisplayID() -> u32;
+        fn CGSessionCopyCurrentDictionary() -> *const c_void;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    unsafe extern "C" {
+        fn CFRelease(object: *const c_void);
+        fn CFStringCreateWithCString(
+            allocator: *const c_void,
+            text: *const std::ffi::c_char,
+            encoding: u32,
+        ) -> *const c_void;
+        fn CFDictionaryGetValue(dictionary: *const c_void, key: *const c_void) -> *const c_void;
+        fn CFBooleanGetValue(boolean: *const c_void) -> u8;
+        fn CFPreferencesCopyAppValue(
+            key: *const c_void,
+            application_id: *const c_void,
+        ) -> *const c_void;
+    }
+
+    pub fn cursor_position() -> Option<Point> {
+        unsafe {
+            let event = CGEventCreate(std::ptr::null());
+            if event.is_null() {
+                return None;
+            }
+            let location = CGEventGetLocation(event);
+            CFRelease(event);
+            Some(Point::new(location.x as f32, location.y as f32))
+        }
+    }
+
+    pub fn idle_duration() -> Option<Duration> {
+        let seconds = unsafe {
+            CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT_TYPE)
+        };
+        Duration::try_from_secs_f64(seconds).ok()
+    }
+
+    pub fn display_bounds() -> Vec<Rectangle> {
+        let mut displays = [0u32; 16];
+        let mut count = 0u32;
+        let ok = unsafe {
+            CGGetActiveDisplayList(displays.len() as u32, displays.as_mut_ptr(), &mut count)
+        };
+        if ok != 0 {
+            return Vec::new();
+        }
+
+        let main = unsafe { CGMainDisplayID() };
+        let mut displays = displays[..count as usize].to_vec();
+        displays.sort_by_key(|display| *display != main);
+        displays
+            .into_iter()
+            .map(|display| {
+                let bounds = unsafe { CGDisplayBounds(display) };
+                Rectangle::new(
+                    Point::new(bounds.origin.x as f32, bounds.origin.y as f32),
+                    Size::new(bounds.size.width as f32, bounds.size.height as f32),
+                )
+            })
+            .collect()
+    }
+
+    /// 深色外观时全局偏好中的 `AppleInterfaceStyle` 为 `Dark`，浅色外观时没有这一项。
+    pub fn light_menu_bar() -> bool {
+        unsafe {
+            let key = CFStringCreateWithCString(
+                std::ptr::null(),
+                c"AppleInterfaceStyle".as_ptr(),
+                UTF8_ENCODING,
+            );
+            // 即 `kCFPreferencesAnyApplication` 常量的值
+            let application = CFStringCreateWithCString(
+                std::ptr::null(),
+                c"kCFPreferencesAnyApplication".as_ptr(),
+                UTF8_ENCODING,
+            );
+            let light = !key.is_null() && !application.is_null() && {
+                let style = CFPreferencesCopyAppValue(key, application);
+                if !style.is_null() {
+                    CFRelease(style);
+                }
+                style.is_null()
+            };
+            for object in [key, application] {
+                if !object.is_null() {
+                    CFRelease(object);
+                }
+            }
+            light
+        }
+    }
+
+    /// 「屏幕共享」或远程控制连接时会话字典里的 `CGSSessionScreenIsShared` 为真。
+    pub fn screen_is_shared() -> bool {
+        unsafe {
+            let session = CGSessionCopyCurrentDictionary();
+            if session.is_null() {
+                return false;
+            }
+            let key = CFStringCreateWithCString(
+                std::ptr::null(),
+                c"CGSSessionScreenIsShared".as_ptr(),
+                UTF8_ENCODING,
+            );
+            let shared = !key.is_null() && {
+                let value = CFDictionaryGetValue(session, key);
+                !value.is_null() && CFBooleanGetValue(value) != 0
+            };
+            if !key.is_null() {
+                CFRelease(key);
+            }
+            CFRelease(session);
+            shared
+        }
+    }
+}
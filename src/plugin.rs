@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::api::{self, ApiClient, FetchError, FetchedSubscriptions, Subscription};
+use crate::config::AppConfig;
+use crate::provider::QuotaProvider;
+
+/// 单次解析可消耗的燃料上限，防止插件死循环卡住刷新。
+const FUEL_LIMIT: u64 = 500_000_000;
+/// 插件线性内存的上限。
+const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+/// 插件返回的 JSON 上限。
+const MAX_OUTPUT_BYTES: usize = 256 * 1024;
+
+/// 插件目录中的 `plugin.toml`。
+///
+/// 主机按 `fetch_url` / `headers` 代为请求，插件只负责把响应体解析为订阅列表；
+/// WASM 模块不导入任何主机函数，无法自行访问网络或文件。
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_wasm")]
+    pub wasm: String,
+    pub fetch_url: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+fn default_wasm() -> String {
+    "provider.wasm".to_string()
+}
+
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    pub dir: PathBuf,
+    pub manifest: PluginManifest,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("fetch: {0}")]
+    Fetch(#[from] FetchError),
+    #[error("wasm: {0}")]
+    Wasm(String),
+    #[error("plugin output exceeds {MAX_OUTPUT_BYTES} bytes")]
+    OutputTooLarge,
+    #[error("invalid plugin output: {0}")]
+    Output(#[from] serde_json::Error),
+}
+
+/// 扫描 `plugins/<name>/plugin.toml`；清单无法解析的目录返回错误说明，便于在设置页展示。
+pub fn discover(dir: &Path) -> Vec<Result<Plugin, String>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<Result<Plugin, String>> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("plugin.toml").is_file())
+        .map(|dir| {
+            let raw = std::fs::read_to_string(dir.join("plugin.toml"))
+                .map_err(|err| format!("{}: {err}", dir.display()))?;
+            let manifest = toml::from_str::<PluginManifest>(&raw)
+                .map_err(|err| format!("{}: {err}", dir.display()))?;
+            Ok(Plugin { dir, manifest })
+        })
+        .collect();
+    plugins.sort_by_key(|plugin| plugin.as_ref().ok().map(|p| p.manifest.name.clone()));
+    plugins
+}
+
+impl Plugin {
+    pub async fn run(&self, client: &ApiClient) -> Result<Vec<Subscription>, PluginError> {
+        let body = api::fetch_raw(client, &self.manifest.fetch_url, &self.manifest.headers).await?;
+        let wasm_path = self.dir.join(&self.manifest.wasm);
+        let output = tokio::task::spawn_blocking(move || parse_with_wasm(&wasm_path, &body))
+            .await
+            .map_err(|err| PluginError::Wasm(err.to_string()))??;
+        Ok(serde_json::from_slice(&output)?)
+    }
+}
+
+/// 以插件作为额度数据源：每次刷新重新扫描插件目录，按名称选用。
+pub struct PluginProvider<'a> {
+    pub dir: &'a Path,
+}
+
+impl QuotaProvider for PluginProvider<'_> {
+    async fn fetch(
+        &self,
+        client: &ApiClient,
+        config: &AppConfig,
+        _preferred_endpoint: Option<&str>,
+    ) -> Result<FetchedSubscriptions, FetchError> {
+        let name = config.plugin_name.trim();
+        if name.is_empty() {
+            return Err(FetchError::MissingConfig);
+        }
+        let plugin = discover(self.dir)
+            .into_iter()
+            .flatten()
+            .find(|plugin| plugin.manifest.name == name)
+            .ok_or_else(|| FetchError::Plugin(format!("{name} not found")))?;
+
+        let subscriptions = plugin.run(client).await.map_err(|err| match err {
+            PluginError::Fetch(err) => err,
+            err => FetchError::Plugin(err.to_string()),
+        })?;
+        Ok(FetchedSubscriptions {
+            subscriptions,
+            endpoint: plugin.manifest.fetch_url,
+            truncated_at: None,
+        })
+    }
+}
+
+/// 插件 ABI：导出 `memory`、`alloc(len) -> ptr` 与 `parse(ptr, len) -> u64`，
+/// 返回值高 32 位为输出指针、低 32 位为长度，输出为订阅列表 JSON。
+fn parse_with_wasm(path: &Path, input: &[u8]) -> Result<Vec<u8>, PluginError> {
+    use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimitsBuilder};
+
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config).map_err(wasm_error)?;
+    let module = Module::from_file(&engine, path).map_err(wasm_error)?;
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MEMORY_LIMIT)
+        .instances(1)
+        .build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    store.set_fuel(FUEL_LIMIT).map_err(wasm_error)?;
+
+    let instance = Instance::new(&mut store, &module, &[]).map_err(wasm_error)?;
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or_else(|| PluginError::Wasm("missing export: memory".to_string()))?;
+    let alloc = instance
+        .get_typed_func::<u32, u32>(&mut store, "alloc")
+        .map_err(wasm_error)?;
+    let parse = instance
+        .get_typed_func::<(u32, u32), u64>(&mut store, "parse")
+        .map_err(wasm_error)?;
+
+    let len = u32::try_from(input.len()).map_err(|_| PluginError::OutputTooLarge)?;
+    let ptr = alloc.call(&mut store, len).map_err(wasm_error)?;
+    memory
+        .write(&mut store, ptr as usize, input)
+        .map_err(wasm_error)?;
+
+    let (out_ptr, out_len) = unpack(parse.call(&mut store, (ptr, len)).map_err(wasm_error)?);
+    if out_len > MAX_OUTPUT_BYTES {
+        return Err(PluginError::OutputTooLarge);
+    }
+    let mut output = vec![0u8; out_len];
+    memory
+        .read(&store, out_ptr, &mut output)
+        .map_err(wasm_error)?;
+    Ok(output)
+}
+
+fn unpack(packed: u64) -> (usize, usize) {
+    ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize)
+}
+
+fn wasm_error(err: impl std::fmt::Display) -> PluginError {
+    PluginError::Wasm(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{PluginManifest, PluginProvider, unpack};
+    use crate::api::{ApiClient, FetchError};
+    use crate::config::AppConfig;
+    use crate::provider::QuotaProvider;
+
+    #[test]
+    fn parses_manifest_with_defaults() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+name = "example"
+fetch_url = "https://quota.example.com/api/usage"
+
+[headers]
+Authorization = "Bearer abc"
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.wasm, "provider.wasm");
+        assert_eq!(manifest.headers["Authorization"], "Bearer abc");
+    }
+
+    #[test]
+    fn provider_reports_missing_or_unknown_plugins() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        let mut config = AppConfig::default();
+        let client = ApiClient::new(&config).unwrap();
+        let provider = PluginProvider {
+            dir: Path::new("/nonexistent/plugins"),
+        };
+
+        let result = runtime.block_on(provider.fetch(&client, &config, None));
+        assert!(matches!(result, Err(FetchError::MissingConfig)));
+
+        config.plugin_name = "example".to_string();
+        let result = runtime.block_on(provider.fetch(&client, &config, None));
+        assert!(matches!(result, Err(FetchError::Plugin(_))));
+    }
+
+    #[test]
+    fn unpacks_pointer_and_length() {
+        assert_eq!(unpack((16 << 32) | 42), (16, 42));
+    }
+}
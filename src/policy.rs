@@ -0,0 +1,231 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::config::{AppConfig, ConfigError};
+
+/// 可由管理员锁定、设置页中不可修改的字段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockedField {
+    ApiBase,
+    RefreshSeconds,
+    Autostart,
+    WebView,
+    Relay,
+}
+
+/// 与配置文件同目录的 `policy.toml`，用于 IT 统一部署：
+///
+/// ```toml
+/// locked = ["api_base", "refresh_seconds", "relay"]
+/// api_base = "https://right.codes"
+/// api_mirrors = ["https://mirror.example"]
+/// min_refresh_seconds = 120
+/// relay_url = ""
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Policy {
+    #[serde(default)]
+    pub locked: Vec<LockedField>,
+    pub api_base: Option<String>,
+    pub api_mirrors: Option<Vec<String>>,
+    pub refresh_seconds: Option<u64>,
+    pub min_refresh_seconds: Option<u64>,
+    pub autostart: Option<bool>,
+    pub web_enabled: Option<bool>,
+    pub relay_url: Option<String>,
+    /// 策略文件存在但无法读取或解析时的错误，此时按 [`Policy::locked_down`] 处理。
+    #[serde(skip)]
+    pub error: Option<String>,
+}
+
+impl Policy {
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)?;
+        Ok(toml::from_str::<Self>(&raw)?)
+    }
+
+    /// 读取策略；文件损坏时不能当作没有策略，否则管理员的限制会全部失效。
+    pub fn load_or_lock(path: &Path) -> Self {
+        Self::load(path).unwrap_or_else(|err| Self::locked_down(err.to_string()))
+    }
+
+    /// 锁定全部受管字段，并关闭网页查看与中继推送这类会把数据发到外部的功能。
+    pub fn locked_down(error: String) -> Self {
+        Self {
+            locked: vec![
+                LockedField::ApiBase,
+                LockedField::RefreshSeconds,
+                LockedField::Autostart,
+                LockedField::WebView,
+                LockedField::Relay,
+            ],
+            web_enabled: Some(false),
+            relay_url: Some(String::new()),
+            error: Some(error),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_managed(&self) -> bool {
+        !self.locked.is_empty() || self.min_refresh_seconds.is_some()
+    }
+
+    pub fn is_locked(&self, field: LockedField) -> bool {
+        self.locked.contains(&field)
+    }
+
+    /// 用策略值覆盖配置；只有被锁定的字段会被强制，刷新下限始终生效。
+    pub fn apply(&self, config: &mut AppConfig) {
+        if self.is_locked(LockedField::ApiBase)
+            && let Some(api_base) = &self.api_base
+        {
+            config.api_base = api_base.clone();
+        }
+
+        if self.is_locked(LockedField::ApiBase)
+            && let Some(mirrors) = &self.api_mirrors
+        {
+            config.api_mirrors = mirrors.clone();
+        }
+
+        if self.is_locked(LockedField::RefreshSeconds)
+            && let Some(seconds) = self.refresh_seconds
+        {
+            config.refresh_seconds = seconds;
+        }
+
+        // 0 表示手动刷新，不会产生请求，不受下限约束
+        if let Some(floor) = self.min_refresh_seconds {
+            for seconds in std::iter::once(&mut config.refresh_seconds)
+                .chain(config.source_refresh_seconds.values_mut())
+                .filter(|seconds| **seconds != 0)
+            {
+                *seconds = (*seconds).max(floor);
+            }
+        }
+
+        if self.is_locked(LockedField::Autostart)
+            && let Some(autostart) = self.autostart
+        {
+            config.autostart = autostart;
+        }
+
+        if self.is_locked(LockedField::WebView)
+            && let Some(enabled) = self.web_enabled
+        {
+            config.web_enabled = enabled;
+        }
+
+        if self.is_locked(LockedField::Relay)
+            && let Some(relay_url) = &self.relay_url
+        {
+            config.relay_url = relay_url.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{LockedField, Policy};
+    use crate::config::AppConfig;
+
+    #[test]
+    fn locked_fields_override_config() {
+        let policy = toml::from_str::<Policy>(
+            r#"
+locked = ["api_base", "relay"]
+api_base = "https://mirror.example"
+relay_url = ""
+"#,
+        )
+        .expect("valid policy");
+
+        let mut config = AppConfig {
+            relay_url: "https://ntfy.sh/leak".to_string(),
+            ..AppConfig::default()
+        };
+        policy.apply(&mut config);
+
+        assert_eq!(config.api_base, "https://mirror.example");
+        assert!(config.relay_url.is_empty());
+        assert!(policy.is_locked(LockedField::ApiBase));
+        assert!(!policy.is_locked(LockedField::RefreshSeconds));
+    }
+
+    #[test]
+    fn unlocked_values_are_not_forced() {
+        let policy = toml::from_str::<Policy>(r#"api_base = "https://mirror.example""#)
+            .expect("valid policy");
+
+        let mut config = AppConfig::default();
+        policy.apply(&mut config);
+
+        assert_eq!(config.api_base, "https://right.codes");
+        assert!(!policy.is_managed());
+    }
+
+    #[test]
+    fn malformed_policy_locks_everything_down() {
+        let path = std::env::temp_dir().join(format!("policy-test-{}.toml", std::process::id()));
+        std::fs::write(&path, "locked = [").unwrap();
+        let policy = Policy::load_or_lock(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut config = AppConfig {
+            web_enabled: true,
+            relay_url: "https://ntfy.sh/leak".to_string(),
+            ..AppConfig::default()
+        };
+        policy.apply(&mut config);
+
+        assert!(policy.error.is_some());
+        assert!(policy.is_locked(LockedField::ApiBase));
+        assert!(policy.is_locked(LockedField::RefreshSeconds));
+        assert!(!config.web_enabled);
+        assert!(config.relay_url.is_empty());
+        assert!(
+            Policy::load_or_lock(Path::new("/nonexistent/policy.toml"))
+                .error
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn refresh_floor_always_applies() {
+        let policy = toml::from_str::<Policy>("min_refresh_seconds = 120").expect("valid policy");
+
+        let mut config = AppConfig::default();
+        config
+            .source_refresh_seconds
+            .insert("subscriptions".to_string(), 30);
+        policy.apply(&mut config);
+
+        assert_eq!(config.refresh_seconds, 120);
+        assert_eq!(config.source_refresh_seconds["subscriptions"], 120);
+        assert!(policy.is_managed());
+    }
+
+    #[test]
+    fn refresh_floor_keeps_manual_refresh() {
+        let policy = toml::from_str::<Policy>("min_refresh_seconds = 120").expect("valid policy");
+
+        let mut config = AppConfig {
+            refresh_seconds: 0,
+            ..AppConfig::default()
+        };
+        config
+            .source_refresh_seconds
+            .insert("usage_summary".to_string(), 0);
+        policy.apply(&mut config);
+
+        assert_eq!(config.refresh_seconds, 0);
+        assert_eq!(config.source_refresh_seconds["usage_summary"], 0);
+    }
+}
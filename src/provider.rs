@@ -0,0 +1,328 @@
+//! 额度数据源：除 RightCode 外，还可以读取 OpenAI、Anthropic 或任意 JSON 接口的额度。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+
+use crate::api::{self, ApiClient, FetchError, FetchedSubscriptions, Subscription};
+use crate::config::{AppConfig, ProviderKind};
+use crate::plugin::PluginProvider;
+
+const OPENAI_CREDIT_GRANTS_URL: &str = "https://api.openai.com/v1/dashboard/billing/credit_grants";
+const ANTHROPIC_MODELS_URL: &str = "https://api.anthropic.com/v1/models";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 一种额度数据源，把各自的接口转换成统一的订阅列表。
+pub trait QuotaProvider {
+    /// `preferred_endpoint` 为上次成功的地址，只有多地址的数据源会用到。
+    fn fetch(
+        &self,
+        client: &ApiClient,
+        config: &AppConfig,
+        preferred_endpoint: Option<&str>,
+    ) -> impl Future<Output = Result<FetchedSubscriptions, FetchError>> + Send;
+}
+
+/// RightCode 订阅列表，支持备用地址。
+pub struct RightCode;
+
+impl QuotaProvider for RightCode {
+    async fn fetch(
+        &self,
+        client: &ApiClient,
+        config: &AppConfig,
+        preferred_endpoint: Option<&str>,
+    ) -> Result<FetchedSubscriptions, FetchError> {
+        api::fetch_subscriptions(client, config, preferred_endpoint).await
+    }
+}
+
+/// OpenAI 赠送/预付额度（`credit_grants`）。
+///
+/// 这是已弃用的 dashboard 接口，普通 `sk-` API key 会被拒绝，只接受从网页端登录会话中取得的
+/// `sess-` 会话密钥，且随时可能失效。
+pub struct OpenAi;
+
+#[derive(Debug, Deserialize)]
+struct CreditGrants {
+    total_granted: f64,
+    total_available: f64,
+}
+
+impl QuotaProvider for OpenAi {
+    async fn fetch(
+        &self,
+        client: &ApiClient,
+        config: &AppConfig,
+        _preferred_endpoint: Option<&str>,
+    ) -> Result<FetchedSubscriptions, FetchError> {
+        let key = config.openai_api_key.trim();
+        if key.is_empty() {
+            return Err(FetchError::MissingConfig);
+        }
+
+        let headers = BTreeMap::from([
+            ("Accept".to_string(), "application/json".to_string()),
+            ("Authorization".to_string(), format!("Bearer {key}")),
+        ]);
+        let body = api::fetch_raw(client, OPENAI_CREDIT_GRANTS_URL, &headers).await?;
+        Ok(FetchedSubscriptions {
+            subscriptions: parse_credit_grants(&body)?,
+            endpoint: OPENAI_CREDIT_GRANTS_URL.to_string(),
+            truncated_at: None,
+        })
+    }
+}
+
+fn parse_credit_grants(body: &[u8]) -> Result<Vec<Subscription>, FetchError> {
+    let grants = serde_json::from_slice::<CreditGrants>(body)?;
+    Ok(vec![Subscription {
+        name: "OpenAI".to_string(),
+        total_quota: grants.total_granted,
+        remaining_quota: grants.total_available,
+        unit: Some("$".to_string()),
+    }])
+}
+
+/// Anthropic 没有公开的余额接口，这里显示响应头中当前速率限制窗口的剩余请求数与 token 数。
+///
+/// `GET /v1/models` 并不保证返回 `anthropic-ratelimit-*` 头，返回的数值也不一定与消息接口的
+/// 限额一致，只能作为参考。
+pub struct Anthropic;
+
+impl QuotaProvider for Anthropic {
+    async fn fetch(
+        &self,
+        client: &ApiClient,
+        config: &AppConfig,
+        _preferred_endpoint: Option<&str>,
+    ) -> Result<FetchedSubscriptions, FetchError> {
+        let key = config.anthropic_api_key.trim();
+        if key.is_empty() {
+            return Err(FetchError::MissingConfig);
+        }
+
+        let headers = BTreeMap::from([
+            ("x-api-key".to_string(), key.to_string()),
+            (
+                "anthropic-version".to_string(),
+                ANTHROPIC_VERSION.to_string(),
+            ),
+        ]);
+        let response_headers = api::fetch_headers(client, ANTHROPIC_MODELS_URL, &headers).await?;
+        Ok(FetchedSubscriptions {
+            subscriptions: parse_rate_limits(&response_headers)?,
+            endpoint: ANTHROPIC_MODELS_URL.to_string(),
+            truncated_at: None,
+        })
+    }
+}
+
+fn parse_rate_limits(headers: &HeaderMap) -> Result<Vec<Subscription>, FetchError> {
+    let header_number = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+    };
+
+    let subscriptions: Vec<Subscription> = [("requests", "请求", "次"), ("tokens", "Token", "")]
+        .into_iter()
+        .filter_map(|(kind, label, unit)| {
+            let total = header_number(&format!("anthropic-ratelimit-{kind}-limit"))?;
+            let remaining = header_number(&format!("anthropic-ratelimit-{kind}-remaining"))?;
+            Some(Subscription {
+                name: format!("Anthropic {label}"),
+                total_quota: total,
+                remaining_quota: remaining,
+                unit: (!unit.is_empty()).then(|| unit.to_string()),
+            })
+        })
+        .collect();
+
+    if subscriptions.is_empty() {
+        return Err(FetchError::MissingField(
+            "anthropic-ratelimit-*".to_string(),
+        ));
+    }
+    Ok(subscriptions)
+}
+
+/// 任意 JSON 接口，按配置的 JSON Pointer 读取名称与额度。
+pub struct GenericJson;
+
+impl QuotaProvider for GenericJson {
+    async fn fetch(
+        &self,
+        client: &ApiClient,
+        config: &AppConfig,
+        _preferred_endpoint: Option<&str>,
+    ) -> Result<FetchedSubscriptions, FetchError> {
+        let url = config.generic_json_url.trim();
+        if url.is_empty() {
+            return Err(FetchError::MissingConfig);
+        }
+
+        let mut headers = BTreeMap::from([("Accept".to_string(), "application/json".to_string())]);
+        let token = config.generic_json_token.trim();
+        if !token.is_empty() {
+            headers.insert("Authorization".to_string(), format!("Bearer {token}"));
+        }
+        let body = api::fetch_raw(client, url, &headers).await?;
+        Ok(FetchedSubscriptions {
+            subscriptions: parse_generic_json(&body, config)?,
+            endpoint: url.to_string(),
+            truncated_at: None,
+        })
+    }
+}
+
+fn parse_generic_json(body: &[u8], config: &AppConfig) -> Result<Vec<Subscription>, FetchError> {
+    let root = serde_json::from_slice::<serde_json::Value>(body)?;
+    let list = config.generic_json_list.trim();
+    let items = if list.is_empty() {
+        std::slice::from_ref(&root)
+    } else {
+        root.pointer(list)
+            .and_then(serde_json::Value::as_array)
+            .map(Vec::as_slice)
+            .ok_or_else(|| FetchError::MissingField(list.to_string()))?
+    };
+
+    let name_pointer = config.generic_json_name.trim();
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let name = (!name_pointer.is_empty())
+                .then(|| item.pointer(name_pointer))
+                .flatten()
+                .and_then(|value| match value {
+                    serde_json::Value::String(name) => Some(name.clone()),
+                    serde_json::Value::Number(number) => Some(number.to_string()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| match items.len() {
+                    1 => ProviderKind::GenericJson.to_string(),
+                    _ => format!("{} {}", ProviderKind::GenericJson, index + 1),
+                });
+            Ok(Subscription {
+                name,
+                total_quota: json_number(item, &config.generic_json_total)?,
+                remaining_quota: json_number(item, &config.generic_json_remaining)?,
+                unit: None,
+            })
+        })
+        .collect()
+}
+
+/// 数字或数字字符串（部分接口把金额序列化为字符串）。
+fn json_number(item: &serde_json::Value, pointer: &str) -> Result<f64, FetchError> {
+    let pointer = pointer.trim();
+    item.pointer(pointer)
+        .and_then(|value| match value {
+            serde_json::Value::Number(number) => number.as_f64(),
+            serde_json::Value::String(text) => text.trim().parse::<f64>().ok(),
+            _ => None,
+        })
+        .ok_or_else(|| FetchError::MissingField(pointer.to_string()))
+}
+
+/// 按配置的数据源拉取额度，额外返回本次请求耗时；`plugins_dir` 只有插件数据源会用到。
+pub async fn fetch_quota_timed(
+    client: &ApiClient,
+    config: &AppConfig,
+    preferred_endpoint: Option<&str>,
+    plugins_dir: &Path,
+) -> (Result<FetchedSubscriptions, FetchError>, Duration) {
+    let started = Instant::now();
+    let result = match config.provider {
+        ProviderKind::RightCode => RightCode.fetch(client, config, preferred_endpoint).await,
+        ProviderKind::OpenAi => OpenAi.fetch(client, config, preferred_endpoint).await,
+        ProviderKind::Anthropic => Anthropic.fetch(client, config, preferred_endpoint).await,
+        ProviderKind::GenericJson => GenericJson.fetch(client, config, preferred_endpoint).await,
+        ProviderKind::Plugin => {
+            let provider = PluginProvider { dir: plugins_dir };
+            provider.fetch(client, config, preferred_endpoint).await
+        }
+    };
+    (result, started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::{parse_credit_grants, parse_generic_json, parse_rate_limits};
+    use crate::api::FetchError;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn parses_openai_credit_grants() {
+        let body = br#"{"object":"credit_summary","total_granted":18.0,"total_used":4.5,"total_available":13.5}"#;
+        let subscriptions = parse_credit_grants(body).unwrap();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].total_quota, 18.0);
+        assert_eq!(subscriptions[0].remaining_quota, 13.5);
+        assert_eq!(subscriptions[0].format_remaining(), "$13.50");
+    }
+
+    #[test]
+    fn parses_anthropic_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("anthropic-ratelimit-requests-limit", "50"),
+            ("anthropic-ratelimit-requests-remaining", "49"),
+            ("anthropic-ratelimit-tokens-limit", "40000"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        // tokens 缺少 remaining，只返回请求数
+        let subscriptions = parse_rate_limits(&headers).unwrap();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].name, "Anthropic 请求");
+        assert_eq!(subscriptions[0].remaining_quota, 49.0);
+
+        assert!(matches!(
+            parse_rate_limits(&HeaderMap::new()),
+            Err(FetchError::MissingField(_))
+        ));
+    }
+
+    #[test]
+    fn reads_generic_json_by_pointer() {
+        let config = AppConfig {
+            generic_json_list: "/data/plans".to_string(),
+            generic_json_name: "/title".to_string(),
+            generic_json_total: "/quota/total".to_string(),
+            generic_json_remaining: "/quota/left".to_string(),
+            ..AppConfig::default()
+        };
+        let body = br#"{"data":{"plans":[
+            {"title":"Pro","quota":{"total":100,"left":"42.5"}},
+            {"quota":{"total":10,"left":0}}
+        ]}}"#;
+
+        let subscriptions = parse_generic_json(body, &config).unwrap();
+        assert_eq!(subscriptions.len(), 2);
+        assert_eq!(subscriptions[0].name, "Pro");
+        assert_eq!(subscriptions[0].remaining_quota, 42.5);
+        assert_eq!(subscriptions[1].name, "通用 JSON 2");
+        assert_eq!(subscriptions[1].total_quota, 10.0);
+    }
+
+    #[test]
+    fn generic_json_defaults_to_single_object() {
+        let config = AppConfig::default();
+        let subscriptions = parse_generic_json(br#"{"total":5,"remaining":2}"#, &config).unwrap();
+        assert_eq!(subscriptions[0].name, "通用 JSON");
+        assert_eq!(subscriptions[0].remaining_quota, 2.0);
+
+        let missing = parse_generic_json(br#"{"total":5}"#, &config);
+        assert!(matches!(missing, Err(FetchError::MissingField(path)) if path == "/remaining"));
+    }
+}
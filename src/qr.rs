@@ -0,0 +1,53 @@
+use qrcode::{Color as QrColor, EcLevel, QrCode};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrMatrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    pub fn encode(data: &str) -> Result<Self, String> {
+        let code = QrCode::with_error_correction_level(data.as_bytes(), EcLevel::L)
+            .map_err(|e| e.to_string())?;
+
+        let width = code.width();
+        let modules = code
+            .to_colors()
+            .into_iter()
+            .map(|color| color == QrColor::Dark)
+            .collect();
+
+        Ok(Self { width, modules })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.width && self.modules[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QrMatrix;
+
+    #[test]
+    fn encodes_square_matrix_with_finder_patterns() {
+        let qr = QrMatrix::encode("https://right.codes/dashboard").expect("encodable");
+        let width = qr.width();
+
+        assert!(width >= 21);
+        assert!(qr.is_dark(0, 0));
+        assert!(qr.is_dark(width - 1, 0));
+        assert!(qr.is_dark(0, width - 1));
+    }
+
+    #[test]
+    fn out_of_bounds_modules_are_light() {
+        let qr = QrMatrix::encode("42").expect("encodable");
+        assert!(!qr.is_dark(qr.width(), 0));
+    }
+}
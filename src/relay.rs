@@ -0,0 +1,111 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key};
+
+use crate::api::ApiClient;
+use crate::web::StatusSnapshot;
+
+/// 配对二维码内容的前缀，手机端据此识别中继地址与密钥。
+const PAIRING_SCHEME: &str = "rcfb-relay";
+
+#[derive(Debug, thiserror::Error)]
+pub enum RelayError {
+    #[error("relay key must be 32 bytes of base64")]
+    InvalidKey,
+    #[error("encrypt failed")]
+    Encrypt,
+    #[error("serialize error: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("request error: {0}")]
+    Request(#[from] reqwest::Error),
+}
+
+pub fn generate_key() -> String {
+    STANDARD.encode(ChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+pub fn pairing_uri(relay_url: &str, key: &str) -> String {
+    format!("{PAIRING_SCHEME}:{}#{}", relay_url.trim(), key.trim())
+}
+
+/// 输出 base64(nonce || ciphertext)，nonce 为 12 字节随机数。
+pub fn encrypt(key: &str, plaintext: &[u8]) -> Result<String, RelayError> {
+    let key_bytes = STANDARD
+        .decode(key.trim())
+        .map_err(|_| RelayError::InvalidKey)?;
+    if key_bytes.len() != 32 {
+        return Err(RelayError::InvalidKey);
+    }
+
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| RelayError::Encrypt)?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(payload))
+}
+
+/// 将加密后的状态以纯文本 POST 到中继（如 `https://ntfy.sh/<topic>`），与查询共用代理设置。
+pub async fn publish(
+    client: ApiClient,
+    relay_url: String,
+    key: String,
+    snapshot: StatusSnapshot,
+) -> Result<(), RelayError> {
+    let plaintext = serde_json::to_vec(&snapshot)?;
+    let body = encrypt(&key, &plaintext)?;
+
+    client
+        .post(relay_url.trim())
+        .header("Content-Type", "text/plain")
+        .body(body)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use chacha20poly1305::aead::{Aead, KeyInit};
+    use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+    use super::{RelayError, encrypt, generate_key, pairing_uri};
+
+    #[test]
+    fn encrypted_payload_round_trips() {
+        let key = generate_key();
+        let sealed = encrypt(&key, b"{\"ratio\":0.42}").expect("encrypts");
+
+        let raw = STANDARD.decode(sealed).expect("base64");
+        let (nonce, ciphertext) = raw.split_at(12);
+        let key_bytes = STANDARD.decode(&key).expect("base64 key");
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .expect("decrypts");
+
+        assert_eq!(plaintext, b"{\"ratio\":0.42}");
+    }
+
+    #[test]
+    fn rejects_short_keys() {
+        let short = STANDARD.encode([0u8; 8]);
+        assert!(matches!(encrypt(&short, b"x"), Err(RelayError::InvalidKey)));
+    }
+
+    #[test]
+    fn builds_pairing_uri() {
+        assert_eq!(
+            pairing_uri(" https://ntfy.sh/abc ", "KEY"),
+            "rcfb-relay:https://ntfy.sh/abc#KEY"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+/// 帧间隔与绘制耗时的平滑系数，避免调试层数字跳动过快。
+const SMOOTHING: f64 = 0.1;
+/// 超过该间隔视为窗口曾经空闲（如打开设置页），不计入帧间隔。
+const MAX_FRAME_GAP: Duration = Duration::from_secs(1);
+
+/// 悬浮球画布中带缓存的图层。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLayer {
+    Base,
+    Chrome,
+    Text,
+}
+
+impl CacheLayer {
+    pub const ALL: [CacheLayer; 3] = [CacheLayer::Base, CacheLayer::Chrome, CacheLayer::Text];
+
+    fn index(self) -> usize {
+        match self {
+            CacheLayer::Base => 0,
+            CacheLayer::Chrome => 1,
+            CacheLayer::Text => 2,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            CacheLayer::Base => "底",
+            CacheLayer::Chrome => "框",
+            CacheLayer::Text => "字",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct CacheCounter {
+    draws: u64,
+    misses: u64,
+}
+
+/// 调试层使用的渲染统计：帧间隔、每帧绘制耗时、图层数与缓存命中率。
+#[derive(Debug, Clone, Default)]
+pub struct RenderStats {
+    last_frame: Option<Instant>,
+    frame_time: Option<Duration>,
+    draw_time: Option<Duration>,
+    geometries: usize,
+    rebuilt: usize,
+    caches: [CacheCounter; 3],
+}
+
+impl RenderStats {
+    pub fn begin_frame(&mut self, now: Instant) {
+        if let Some(last) = self.last_frame {
+            let gap = now.saturating_duration_since(last);
+            if gap <= MAX_FRAME_GAP {
+                self.frame_time = Some(smooth(self.frame_time, gap));
+            }
+        }
+        self.last_frame = Some(now);
+        self.rebuilt = 0;
+    }
+
+    pub fn record_cache(&mut self, layer: CacheLayer, missed: bool) {
+        let counter = &mut self.caches[layer.index()];
+        counter.draws += 1;
+        if missed {
+            counter.misses += 1;
+            self.rebuilt += 1;
+        }
+    }
+
+    /// `rebuilt` 为本帧未经缓存、重新生成的图层数（如每帧变化的水面）。
+    pub fn end_frame(&mut self, draw_time: Duration, geometries: usize, rebuilt: usize) {
+        self.draw_time = Some(smooth(self.draw_time, draw_time));
+        self.geometries = geometries;
+        self.rebuilt += rebuilt;
+    }
+
+    pub fn hit_rate(&self, layer: CacheLayer) -> Option<f32> {
+        let counter = self.caches[layer.index()];
+        (counter.draws > 0).then(|| (counter.draws - counter.misses) as f32 / counter.draws as f32)
+    }
+
+    /// 调试层显示的文字，每项一行。
+    pub fn lines(&self) -> Vec<String> {
+        let frame = match self.frame_time {
+            Some(frame) if !frame.is_zero() => format!(
+                "帧 {:.1}ms · {:.0}fps",
+                millis(frame),
+                1.0 / frame.as_secs_f64()
+            ),
+            _ => "帧 --".to_string(),
+        };
+        let draw = format!(
+            "绘制 {:.2}ms · 图层 {}/{}",
+            self.draw_time.map(millis).unwrap_or_default(),
+            self.rebuilt,
+            self.geometries
+        );
+        let caches = CacheLayer::ALL
+            .into_iter()
+            .map(|layer| match self.hit_rate(layer) {
+                Some(rate) => format!("{}{:.0}%", layer.label(), rate * 100.0),
+                None => format!("{}--", layer.label()),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        vec![frame, draw, format!("命中 {caches}")]
+    }
+}
+
+fn smooth(previous: Option<Duration>, sample: Duration) -> Duration {
+    match previous {
+        Some(previous) => Duration::from_secs_f64(
+            previous.as_secs_f64() * (1.0 - SMOOTHING) + sample.as_secs_f64() * SMOOTHING,
+        ),
+        None => sample,
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{CacheLayer, RenderStats};
+
+    #[test]
+    fn tracks_cache_hit_rates_per_layer() {
+        let mut stats = RenderStats::default();
+        stats.record_cache(CacheLayer::Text, true);
+        stats.record_cache(CacheLayer::Text, false);
+        stats.record_cache(CacheLayer::Text, false);
+        stats.record_cache(CacheLayer::Text, false);
+        assert_eq!(stats.hit_rate(CacheLayer::Text), Some(0.75));
+        assert_eq!(stats.hit_rate(CacheLayer::Base), None);
+    }
+
+    #[test]
+    fn smooths_frame_time_and_skips_idle_gaps() {
+        let mut stats = RenderStats::default();
+        let start = Instant::now();
+        stats.begin_frame(start);
+        stats.begin_frame(start + Duration::from_millis(20));
+        assert_eq!(stats.frame_time, Some(Duration::from_millis(20)));
+
+        stats.begin_frame(start + Duration::from_secs(10));
+        assert_eq!(stats.frame_time, Some(Duration::from_millis(20)));
+
+        stats.begin_frame(start + Duration::from_secs(10) + Duration::from_millis(30));
+        let frame = stats.frame_time.unwrap().as_secs_f64() * 1000.0;
+        assert!((frame - 21.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn formats_overlay_lines() {
+        let mut stats = RenderStats::default();
+        stats.begin_frame(Instant::now());
+        stats.record_cache(CacheLayer::Base, false);
+        stats.end_frame(Duration::from_micros(400), 4, 1);
+        let lines = stats.lines();
+        assert_eq!(lines[0], "帧 --");
+        assert_eq!(lines[1], "绘制 0.40ms · 图层 1/4");
+        assert_eq!(lines[2], "命中 底100% 框-- 字--");
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::about;
+use crate::api::ApiClient;
+
+/// 同一抓取错误连续出现多少次后上报（每轮连续失败只报一次）。
+const REPEAT_THRESHOLD: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    Panic,
+    FetchError,
+}
+
+/// 上报内容：只有版本、平台与错误信息，不含账号标识、token 与 cookie。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub kind: ReportKind,
+    pub version: String,
+    pub platform: String,
+    pub message: String,
+    pub occurrences: u32,
+    pub at: u64,
+}
+
+impl ErrorReport {
+    pub fn new(kind: ReportKind, message: String, occurrences: u32) -> Self {
+        Self {
+            kind,
+            version: about::VERSION.to_string(),
+            platform: about::build_info(),
+            message,
+            occurrences,
+            at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        }
+    }
+
+    /// 把消息中出现的凭据替换为 `***`，并去掉链接的查询参数。
+    pub fn sanitized(mut self, secrets: &[&str]) -> Self {
+        let mut message = strip_query_strings(&self.message);
+        for secret in secrets.iter().map(|secret| secret.trim()) {
+            if secret.len() >= 4 {
+                message = message.replace(secret, "***");
+            }
+        }
+        self.message = message;
+        self
+    }
+}
+
+fn strip_query_strings(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut skipping = false;
+    for ch in message.chars() {
+        if ch.is_whitespace() || ch == ')' {
+            skipping = false;
+        } else if ch == '?' && current_word_is_url(&stripped) {
+            skipping = true;
+        }
+        if !skipping {
+            stripped.push(ch);
+        }
+    }
+    stripped
+}
+
+fn current_word_is_url(text: &str) -> bool {
+    text.rsplit(char::is_whitespace)
+        .next()
+        .is_some_and(|word| word.contains("://"))
+}
+
+/// 统计连续出现的同一抓取错误。
+#[derive(Debug, Clone, Default)]
+pub struct RepeatedErrors {
+    message: Option<String>,
+    count: u32,
+}
+
+impl RepeatedErrors {
+    /// 记录一次失败，连续次数刚达到阈值时返回次数。
+    pub fn observe(&mut self, message: &str) -> Option<u32> {
+        if self.message.as_deref() == Some(message) {
+            self.count += 1;
+        } else {
+            self.message = Some(message.to_string());
+            self.count = 1;
+        }
+        (self.count == REPEAT_THRESHOLD).then_some(self.count)
+    }
+
+    pub fn reset(&mut self) {
+        self.message = None;
+        self.count = 0;
+    }
+}
+
+/// 崩溃时先把报告写到本地，下次启动再上传；崩溃中的进程无法可靠地发起网络请求。
+pub fn install_panic_hook(path: PathBuf) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = ErrorReport::new(ReportKind::Panic, info.to_string(), 1);
+        if let Ok(json) = serde_json::to_vec(&report) {
+            let _ = std::fs::write(&path, json);
+        }
+        previous(info);
+    }));
+}
+
+/// 取出上次崩溃留下的报告并删除文件。
+pub fn take_pending(path: &Path) -> Option<ErrorReport> {
+    let bytes = std::fs::read(path).ok()?;
+    let _ = std::fs::remove_file(path);
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// 以 JSON POST 到用户配置的地址（如自建 Sentry 的接收端），与查询共用代理设置。
+pub async fn upload(
+    client: ApiClient,
+    url: String,
+    report: ErrorReport,
+) -> Result<(), reqwest::Error> {
+    client
+        .post(url.trim())
+        .json(&report)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorReport, RepeatedErrors, ReportKind};
+
+    #[test]
+    fn strips_credentials_and_query_strings() {
+        let report = ErrorReport::new(
+            ReportKind::FetchError,
+            "request error: error sending request for url \
+             (https://right.codes/subscriptions/list?page=2&key=abc): Bearer sk-secret-token"
+                .to_string(),
+            3,
+        )
+        .sanitized(&["Bearer sk-secret-token", "", "cf"]);
+
+        assert_eq!(
+            report.message,
+            "request error: error sending request for url \
+             (https://right.codes/subscriptions/list): ***"
+        );
+    }
+
+    #[test]
+    fn reports_once_per_run_of_repeated_errors() {
+        let mut errors = RepeatedErrors::default();
+        assert_eq!(errors.observe("timeout"), None);
+        assert_eq!(errors.observe("timeout"), None);
+        assert_eq!(errors.observe("timeout"), Some(3));
+        assert_eq!(errors.observe("timeout"), None);
+
+        assert_eq!(errors.observe("HTTP 502"), None);
+        errors.reset();
+        assert_eq!(errors.observe("HTTP 502"), None);
+    }
+}
@@ -0,0 +1,400 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+    Weekday,
+};
+
+use crate::config::RefreshSource;
+
+/// 一天内的时段，如 `01:00-08:00`；结束早于开始时视为跨越午夜。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl TimeRange {
+    pub fn parse(input: &str) -> Option<Self> {
+        let (start, end) = input.trim().split_once('-')?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+
+        if start == end {
+            return None;
+        }
+
+        Some(Self { start, end })
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// 空字符串表示未启用，也是合法输入。
+pub fn is_valid_optional_range(input: &str) -> bool {
+    input.trim().is_empty() || TimeRange::parse(input).is_some()
+}
+
+pub fn is_quiet_now(quiet_hours: &str, timezone: &str) -> bool {
+    TimeRange::parse(quiet_hours).is_some_and(|range| range.contains(local_now(timezone).time()))
+}
+
+/// 下班时段：落在时段内，或开启周末选项时的周六、周日全天。
+pub fn is_off_hours(off_hours: &str, weekends: bool, now: NaiveDateTime) -> bool {
+    let weekend = matches!(now.weekday(), Weekday::Sat | Weekday::Sun);
+    (weekends && weekend)
+        || TimeRange::parse(off_hours).is_some_and(|range| range.contains(now.time()))
+}
+
+/// 解析固定时区，如 `+08:00`、`-0530`、`UTC`；空字符串表示跟随系统时区。
+pub fn parse_utc_offset(input: &str) -> Option<FixedOffset> {
+    let trimmed = input.trim();
+    if trimmed.eq_ignore_ascii_case("utc") || trimmed.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match trimmed.as_bytes().first()? {
+        b'+' => (1, &trimmed[1..]),
+        b'-' => (-1, &trimmed[1..]),
+        _ => return None,
+    };
+
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if digits.len() != 2 && digits.len() != 4 {
+        return None;
+    }
+
+    let hours = digits.get(0..2)?.parse::<i32>().ok()?;
+    let minutes = digits
+        .get(2..)
+        .filter(|m| !m.is_empty())
+        .map_or(Some(0), |m| m.parse::<i32>().ok())?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+pub fn is_valid_timezone(input: &str) -> bool {
+    input.trim().is_empty() || parse_utc_offset(input).is_some()
+}
+
+/// 按配置的时区返回当前的本地时间（无效或为空时使用系统时区）。
+pub fn local_now(timezone: &str) -> NaiveDateTime {
+    to_local(Utc::now(), timezone)
+}
+
+pub fn format_timestamp(time: SystemTime, timezone: &str) -> String {
+    let utc: DateTime<Utc> = time.into();
+    match parse_utc_offset(timezone) {
+        Some(offset) => utc
+            .with_timezone(&offset)
+            .format("%Y-%m-%d %H:%M:%S %:z")
+            .to_string(),
+        None => utc
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+    }
+}
+
+pub fn format_time_of_day(time: SystemTime, timezone: &str) -> String {
+    to_local(time.into(), timezone).format("%H:%M").to_string()
+}
+
+/// Unix 秒对应的本地日期。
+pub fn local_date_of(unix_seconds: u64, timezone: &str) -> NaiveDate {
+    let utc = DateTime::<Utc>::from_timestamp(unix_seconds as i64, 0).unwrap_or_default();
+    to_local(utc, timezone).date()
+}
+
+/// 该时刻距本地当天零点的秒数。
+pub fn seconds_since_midnight(unix_seconds: u64, timezone: &str) -> u64 {
+    let utc = DateTime::<Utc>::from_timestamp(unix_seconds as i64, 0).unwrap_or_default();
+    u64::from(to_local(utc, timezone).time().num_seconds_from_midnight())
+}
+
+fn to_local(utc: DateTime<Utc>, timezone: &str) -> NaiveDateTime {
+    match parse_utc_offset(timezone) {
+        Some(offset) => utc.with_timezone(&offset).naive_local(),
+        None => utc.with_timezone(&Local).naive_local(),
+    }
+}
+
+/// 以账单日为锚点的计费周期，`end` 为下一周期的起始日（不含）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BillingPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl BillingPeriod {
+    /// `billing_day` 为 0 表示未启用；大于当月天数时取当月最后一天。
+    pub fn containing(date: NaiveDate, billing_day: u32) -> Option<Self> {
+        if !(1..=31).contains(&billing_day) {
+            return None;
+        }
+
+        let (year, month) = (date.year(), date.month());
+        let this_anchor = anchor_date(year, month, billing_day)?;
+
+        let period = if date >= this_anchor {
+            let (next_year, next_month) = shift_month(year, month, 1);
+            Self {
+                start: this_anchor,
+                end: anchor_date(next_year, next_month, billing_day)?,
+            }
+        } else {
+            let (prev_year, prev_month) = shift_month(year, month, -1);
+            Self {
+                start: anchor_date(prev_year, prev_month, billing_day)?,
+                end: this_anchor,
+            }
+        };
+
+        Some(period)
+    }
+
+    pub fn current(billing_day: u32, timezone: &str) -> Option<Self> {
+        Self::containing(local_now(timezone).date(), billing_day)
+    }
+
+    /// 周期内第几天，从 1 开始。
+    pub fn day_number(&self, date: NaiveDate) -> i64 {
+        (date - self.start).num_days() + 1
+    }
+
+    pub fn length_days(&self) -> i64 {
+        (self.end - self.start).num_days()
+    }
+}
+
+fn shift_month(year: i32, month: u32, delta: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + delta;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+fn anchor_date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    (1..=day)
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+}
+
+/// 由统一时钟驱动的刷新计时：每个刷新间隔分别记录下次到期时间。
+#[derive(Debug, Clone, Default)]
+pub struct RefreshClock {
+    due: BTreeMap<Duration, Instant>,
+}
+
+impl RefreshClock {
+    /// 返回 `now` 时已到期的数据源；新出现的间隔从 `now` 开始计时，不再使用的间隔被丢弃。
+    pub fn take_due(
+        &mut self,
+        groups: Vec<(Duration, Vec<RefreshSource>)>,
+        now: Instant,
+    ) -> Vec<RefreshSource> {
+        self.due
+            .retain(|interval, _| groups.iter().any(|(i, _)| i == interval));
+
+        let mut sources = Vec::new();
+        for (interval, group) in groups {
+            let next = self.due.entry(interval).or_insert(now + interval);
+            if now >= *next {
+                *next = now + interval;
+                sources.extend(group);
+            }
+        }
+        sources
+    }
+
+    /// 暂停期间（如打开设置页）清空计时，恢复后重新开始。
+    pub fn reset(&mut self) {
+        self.due.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::{
+        BillingPeriod, RefreshClock, TimeRange, format_timestamp, is_off_hours,
+        is_valid_optional_range, parse_utc_offset,
+    };
+    use crate::config::RefreshSource;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).expect("valid time")
+    }
+
+    #[test]
+    fn parses_daytime_range() {
+        let range = TimeRange::parse("09:00 - 18:30").expect("valid range");
+        assert!(range.contains(at(9, 0)));
+        assert!(range.contains(at(18, 29)));
+        assert!(!range.contains(at(18, 30)));
+        assert!(!range.contains(at(8, 59)));
+    }
+
+    #[test]
+    fn overnight_range_wraps_midnight() {
+        let range = TimeRange::parse("23:00-08:00").expect("valid range");
+        assert!(range.contains(at(23, 30)));
+        assert!(range.contains(at(0, 0)));
+        assert!(range.contains(at(7, 59)));
+        assert!(!range.contains(at(8, 0)));
+        assert!(!range.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        assert!(TimeRange::parse("25:00-08:00").is_none());
+        assert!(TimeRange::parse("01:00").is_none());
+        assert!(TimeRange::parse("08:00-08:00").is_none());
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("valid date")
+    }
+
+    #[test]
+    fn billing_period_starts_on_billing_day() {
+        let period = BillingPeriod::containing(date(2026, 10, 20), 15).expect("enabled");
+        assert_eq!(period.start, date(2026, 10, 15));
+        assert_eq!(period.end, date(2026, 11, 15));
+        assert_eq!(period.day_number(date(2026, 10, 20)), 6);
+        assert_eq!(period.length_days(), 31);
+    }
+
+    #[test]
+    fn billing_period_before_anchor_uses_previous_month() {
+        let period = BillingPeriod::containing(date(2026, 1, 3), 15).expect("enabled");
+        assert_eq!(period.start, date(2025, 12, 15));
+        assert_eq!(period.end, date(2026, 1, 15));
+    }
+
+    #[test]
+    fn billing_day_clamps_to_short_months() {
+        let period = BillingPeriod::containing(date(2026, 2, 28), 31).expect("enabled");
+        assert_eq!(period.start, date(2026, 2, 28));
+        assert_eq!(period.end, date(2026, 3, 31));
+        assert_eq!(period.day_number(date(2026, 2, 28)), 1);
+    }
+
+    #[test]
+    fn billing_day_zero_disables_period() {
+        assert!(BillingPeriod::containing(date(2026, 2, 28), 0).is_none());
+    }
+
+    #[test]
+    fn off_hours_cover_the_range_and_optionally_weekends() {
+        // 2024-06-14 是周五，06-15 是周六
+        let friday = date(2024, 6, 14);
+        let saturday = date(2024, 6, 15);
+
+        assert!(is_off_hours(
+            "18:00-09:00",
+            false,
+            friday.and_time(at(20, 0))
+        ));
+        assert!(!is_off_hours(
+            "18:00-09:00",
+            false,
+            friday.and_time(at(10, 0))
+        ));
+        assert!(!is_off_hours(
+            "18:00-09:00",
+            false,
+            saturday.and_time(at(10, 0))
+        ));
+        assert!(is_off_hours(
+            "18:00-09:00",
+            true,
+            saturday.and_time(at(10, 0))
+        ));
+        assert!(is_off_hours("", true, saturday.and_time(at(10, 0))));
+        assert!(!is_off_hours("", false, saturday.and_time(at(10, 0))));
+    }
+
+    #[test]
+    fn parses_fixed_utc_offsets() {
+        assert_eq!(
+            parse_utc_offset("+08:00").map(|o| o.local_minus_utc()),
+            Some(8 * 3600)
+        );
+        assert_eq!(
+            parse_utc_offset("-0530").map(|o| o.local_minus_utc()),
+            Some(-(5 * 3600 + 30 * 60))
+        );
+        assert_eq!(
+            parse_utc_offset("+09").map(|o| o.local_minus_utc()),
+            Some(9 * 3600)
+        );
+        assert_eq!(
+            parse_utc_offset("UTC").map(|o| o.local_minus_utc()),
+            Some(0)
+        );
+        assert!(parse_utc_offset("").is_none());
+        assert!(parse_utc_offset("Asia/Shanghai").is_none());
+        assert!(parse_utc_offset("+25:00").is_none());
+    }
+
+    #[test]
+    fn formats_timestamp_in_fixed_offset() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(
+            format_timestamp(time, "+08:00"),
+            "2023-11-15 06:13:20 +08:00"
+        );
+    }
+
+    #[test]
+    fn empty_input_disables_range() {
+        assert!(is_valid_optional_range("  "));
+        assert!(!is_valid_optional_range("abc"));
+    }
+
+    #[test]
+    fn refresh_clock_fires_each_interval_independently() {
+        let mut clock = RefreshClock::default();
+        let start = std::time::Instant::now();
+        let groups = || {
+            vec![
+                (Duration::from_secs(60), vec![RefreshSource::Subscriptions]),
+                (Duration::from_secs(300), vec![RefreshSource::UsageSummary]),
+            ]
+        };
+
+        assert!(clock.take_due(groups(), start).is_empty());
+        assert_eq!(
+            clock.take_due(groups(), start + Duration::from_secs(60)),
+            [RefreshSource::Subscriptions]
+        );
+        assert!(
+            clock
+                .take_due(groups(), start + Duration::from_secs(61))
+                .is_empty()
+        );
+        assert_eq!(
+            clock.take_due(groups(), start + Duration::from_secs(300)),
+            [RefreshSource::Subscriptions, RefreshSource::UsageSummary]
+        );
+
+        clock.reset();
+        assert!(
+            clock
+                .take_due(groups(), start + Duration::from_secs(600))
+                .is_empty()
+        );
+    }
+}
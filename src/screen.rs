@@ -0,0 +1,104 @@
+use iced::{Point, Rectangle, Size};
+
+/// 窗口在任一显示器内露出的宽高都不足该值（逻辑像素）时视为跑到屏幕外。
+const MIN_VISIBLE: f32 = 40.0;
+/// 重置位置时与工作区右下角保持的距离。
+const HOME_MARGIN: f32 = 48.0;
+
+/// 窗口不在任何显示器内时，返回拉回到最近显示器工作区内的位置。
+/// `monitors` 为空（平台不支持枚举显示器）时不做判断。
+pub fn rescue_position(window: Rectangle, monitors: &[Rectangle]) -> Option<Point> {
+    let visible = monitors.iter().any(|monitor| {
+        monitor.intersection(&window).is_some_and(|shown| {
+            shown.width >= MIN_VISIBLE.min(window.width)
+                && shown.height >= MIN_VISIBLE.min(window.height)
+        })
+    });
+    if visible {
+        return None;
+    }
+
+    let center = window.center();
+    let nearest = monitors
+        .iter()
+        .min_by(|a, b| distance_to(**a, center).total_cmp(&distance_to(**b, center)))?;
+    Some(clamp_into(window.position(), window.size(), *nearest))
+}
+
+/// 「重置位置」：主显示器工作区的右下角；不知道显示器布局时放到左上角附近。
+pub fn home_position(size: f32, monitors: &[Rectangle]) -> Point {
+    match monitors.first() {
+        Some(primary) => clamp_into(
+            Point::new(
+                primary.x + primary.width - size - HOME_MARGIN,
+                primary.y + primary.height - size - HOME_MARGIN,
+            ),
+            Size::new(size, size),
+            *primary,
+        ),
+        None => Point::new(HOME_MARGIN, HOME_MARGIN),
+    }
+}
+
+fn distance_to(rect: Rectangle, point: Point) -> f32 {
+    let clamped = Point::new(
+        point.x.clamp(rect.x, rect.x + rect.width),
+        point.y.clamp(rect.y, rect.y + rect.height),
+    );
+    clamped.distance(point)
+}
+
+fn clamp_into(position: Point, size: Size, area: Rectangle) -> Point {
+    let right = (area.x + area.width - size.width).max(area.x);
+    let bottom = (area.y + area.height - size.height).max(area.y);
+    Point::new(
+        position.x.clamp(area.x, right),
+        position.y.clamp(area.y, bottom),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::{Point, Rectangle, Size};
+
+    use super::{home_position, rescue_position};
+
+    fn monitors() -> Vec<Rectangle> {
+        vec![
+            Rectangle::new(Point::ORIGIN, Size::new(1920.0, 1040.0)),
+            Rectangle::new(Point::new(1920.0, 0.0), Size::new(1280.0, 984.0)),
+        ]
+    }
+
+    fn ball(x: f32, y: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(120.0, 120.0))
+    }
+
+    #[test]
+    fn leaves_visible_windows_alone() {
+        assert_eq!(rescue_position(ball(1800.0, 900.0), &monitors()), None);
+        // 跨两块屏幕、或大半露在外面但仍可抓到
+        assert_eq!(rescue_position(ball(1880.0, 400.0), &monitors()), None);
+        assert_eq!(rescue_position(ball(-70.0, 300.0), &monitors()), None);
+        assert_eq!(rescue_position(ball(-5000.0, 0.0), &[]), None);
+    }
+
+    #[test]
+    fn pulls_lost_windows_into_the_nearest_monitor() {
+        // 副屏拔掉后留在原来副屏的位置
+        assert_eq!(
+            rescue_position(ball(3400.0, 500.0), &monitors()),
+            Some(Point::new(3080.0, 500.0))
+        );
+        assert_eq!(
+            rescue_position(ball(-400.0, 1200.0), &monitors()),
+            Some(Point::new(0.0, 920.0))
+        );
+    }
+
+    #[test]
+    fn homes_to_the_primary_work_area() {
+        assert_eq!(home_position(120.0, &monitors()), Point::new(1752.0, 872.0));
+        assert_eq!(home_position(120.0, &[]), Point::new(48.0, 48.0));
+    }
+}
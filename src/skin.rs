@@ -0,0 +1,331 @@
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use iced::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+
+/// `#RRGGBB` 或 `#RRGGBBAA` 形式的颜色。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HexColor(pub Color);
+
+impl HexColor {
+    fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self(Color::from_rgba8(r, g, b, a as f32 / 255.0))
+    }
+
+    pub fn parse(input: &str) -> Option<Self> {
+        let hex = input.trim().strip_prefix('#')?;
+        if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+        Some(Self::rgba8(channel(0)?, channel(2)?, channel(4)?, alpha))
+    }
+}
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value).ok_or_else(|| format!("invalid color: {value}"))
+    }
+}
+
+impl From<HexColor> for String {
+    fn from(color: HexColor) -> Self {
+        let [r, g, b, a] = color.0.into_rgba8();
+        if a == 255 {
+            format!("#{r:02X}{g:02X}{b:02X}")
+        } else {
+            format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+        }
+    }
+}
+
+/// 悬浮球皮肤（`skins/<name>.toml` 或 `themes/<name>.toml`），未填写的颜色沿用默认配色；
+/// 拼错的键不会被静默忽略。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BallTheme {
+    pub name: String,
+    pub author: String,
+    pub background: HexColor,
+    pub water_top: HexColor,
+    /// 水面渐变的中间色，未填写时只在顶部与底部两色之间过渡。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub water_mid: Option<HexColor>,
+    pub water_bottom: HexColor,
+    pub outline: HexColor,
+    pub text: HexColor,
+}
+
+impl Default for BallTheme {
+    fn default() -> Self {
+        Self {
+            name: "默认".to_string(),
+            author: String::new(),
+            background: HexColor::rgba8(12, 25, 45, 245),
+            water_top: HexColor::rgba8(0, 255, 200, 200),
+            water_mid: None,
+            water_bottom: HexColor::rgba8(0, 136, 255, 220),
+            outline: HexColor::rgba8(0, 255, 136, 220),
+            text: HexColor::rgba8(0, 255, 200, 245),
+        }
+    }
+}
+
+impl BallTheme {
+    /// 所有颜色的透明度乘以 `opacity`，用于整体调淡悬浮球。
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        let opacity = opacity.clamp(0.0, 1.0);
+        for color in [
+            &mut self.background,
+            &mut self.water_top,
+            &mut self.water_bottom,
+            &mut self.outline,
+            &mut self.text,
+        ]
+        .into_iter()
+        .chain(self.water_mid.as_mut())
+        {
+            color.0.a *= opacity;
+        }
+        self
+    }
+}
+
+/// 主题市场索引（JSON）。
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeIndex {
+    #[serde(default)]
+    pub themes: Vec<BallTheme>,
+}
+
+/// 扫描皮肤目录的结果：可用的皮肤与无法加载的文件（`文件名: 原因`）。
+#[derive(Debug, Clone, Default)]
+pub struct SkinScan {
+    pub themes: Vec<BallTheme>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SkinStore {
+    /// 从主题市场安装的皮肤。
+    pub dir: PathBuf,
+    /// 用户自己编写的皮肤，修改后自动重新加载。
+    pub themes_dir: PathBuf,
+}
+
+impl SkinStore {
+    /// 两个目录中的皮肤按名称排序；无法解析或重名的文件记入错误，不影响其他皮肤。
+    pub fn scan(&self) -> SkinScan {
+        let mut scan = SkinScan::default();
+        for path in self.files() {
+            let file = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let theme = std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|raw| parse_theme(&raw, &stem));
+            match theme {
+                Ok(theme) if scan.themes.iter().any(|other| other.name == theme.name) => {
+                    scan.errors
+                        .push(format!("{file}: 与其他皮肤重名「{}」，已忽略", theme.name));
+                }
+                Ok(theme) => scan.themes.push(theme),
+                Err(err) => scan.errors.push(format!("{file}: {err}")),
+            }
+        }
+        scan.themes.sort_by(|a, b| a.name.cmp(&b.name));
+        scan
+    }
+
+    /// 皮肤文件及其修改时间，变化时重新扫描。
+    pub fn fingerprint(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        self.files()
+            .into_iter()
+            .map(|path| {
+                let modified = std::fs::metadata(&path)
+                    .and_then(|meta| meta.modified())
+                    .ok();
+                (path, modified)
+            })
+            .collect()
+    }
+
+    /// 自定义目录在前，同名时以用户编写的为准。
+    fn files(&self) -> Vec<PathBuf> {
+        [&self.themes_dir, &self.dir]
+            .into_iter()
+            .flat_map(|dir| {
+                let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                    .collect();
+                paths.sort();
+                paths
+            })
+            .collect()
+    }
+
+    pub fn install(&self, theme: &BallTheme) -> Result<PathBuf, ConfigError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let stem = file_stem(&theme.name);
+        let path = self.dir.join(format!("{stem}.toml"));
+        std::fs::write(&path, toml::to_string_pretty(theme)?)?;
+
+        // 旧版本安装时文件名不带哈希，重新安装同一皮肤后删掉旧文件，避免扫描时重名
+        if stem != theme.name.trim()
+            && let Some((legacy, _)) = stem.rsplit_once('-')
+        {
+            let legacy = self.dir.join(format!("{legacy}.toml"));
+            let same_theme = std::fs::read_to_string(&legacy)
+                .ok()
+                .and_then(|raw| parse_theme(&raw, "").ok())
+                .is_some_and(|old| old.name == theme.name.trim());
+            if same_theme {
+                std::fs::remove_file(&legacy)?;
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// 解析并校验一个皮肤文件；未写 `name` 时以文件名作为皮肤名。
+fn parse_theme(raw: &str, stem: &str) -> Result<BallTheme, String> {
+    let table: toml::Table = toml::from_str(raw).map_err(|err| err.message().to_string())?;
+    let named = table.contains_key("name");
+    let mut theme: BallTheme = toml::Value::Table(table)
+        .try_into()
+        .map_err(|err: toml::de::Error| err.message().to_string())?;
+    if !named {
+        theme.name = stem.to_string();
+    }
+    theme.name = theme.name.trim().to_string();
+    if theme.name.is_empty() {
+        return Err("皮肤名称为空".to_string());
+    }
+    Ok(theme)
+}
+
+/// 皮肤名转为安全的文件名，防止索引中的名称跳出皮肤目录。
+///
+/// 有字符被替换时追加名称的哈希，避免「霓虹 夜」与「霓虹_夜」写进同一个文件互相覆盖。
+fn file_stem(name: &str) -> String {
+    let name = name.trim();
+    let stem: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "theme".to_string()
+    } else if stem != name {
+        format!("{stem}-{:08x}", fnv1a(name))
+    } else {
+        stem
+    }
+}
+
+/// 32 位 FNV-1a，结果不随编译器版本变化，同名皮肤重新安装时仍写入原文件。
+fn fnv1a(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BallTheme, HexColor, ThemeIndex, file_stem, parse_theme};
+
+    #[test]
+    fn parses_hex_colors_with_optional_alpha() {
+        let color = HexColor::parse("#FF8000").unwrap();
+        assert_eq!(color.0.into_rgba8(), [255, 128, 0, 255]);
+        let color = HexColor::parse("#00000080").unwrap();
+        assert_eq!(color.0.into_rgba8()[3], 128);
+        assert!(HexColor::parse("FF8000").is_none());
+        assert!(HexColor::parse("#GG0000").is_none());
+        assert_eq!(String::from(HexColor::parse("#0a0b0c").unwrap()), "#0A0B0C");
+    }
+
+    #[test]
+    fn partial_theme_uses_default_colors() {
+        let index: ThemeIndex = serde_json::from_str(
+            r##"{ "themes": [{ "name": "Sunset", "water_top": "#FF7A00" }] }"##,
+        )
+        .unwrap();
+        let theme = &index.themes[0];
+        assert_eq!(theme.name, "Sunset");
+        assert_eq!(theme.water_top.0.into_rgba8(), [255, 122, 0, 255]);
+        assert_eq!(theme.outline, BallTheme::default().outline);
+    }
+
+    #[test]
+    fn keeps_gradient_mid_stop_when_installing() {
+        let index: ThemeIndex = serde_json::from_str(
+            r##"{ "themes": [{ "name": "Sunset", "water_mid": "#FF4080" }] }"##,
+        )
+        .unwrap();
+        let raw = toml::to_string_pretty(&index.themes[0]).unwrap();
+        let theme = parse_theme(&raw, "sunset").unwrap();
+        assert_eq!(theme.water_mid, HexColor::parse("#FF4080"));
+        assert!(
+            !toml::to_string_pretty(&BallTheme::default())
+                .unwrap()
+                .contains("water_mid")
+        );
+    }
+
+    #[test]
+    fn validates_custom_theme_files() {
+        let theme = parse_theme("water_top = \"#FF7A00\"", "sunset").unwrap();
+        assert_eq!(theme.name, "sunset");
+        assert_eq!(theme.outline, BallTheme::default().outline);
+
+        let theme = parse_theme("name = \" 霓虹 \"", "neon").unwrap();
+        assert_eq!(theme.name, "霓虹");
+
+        let err = parse_theme("outline = \"#12345\"", "bad").unwrap_err();
+        assert!(err.contains("#12345"), "{err}");
+        let err = parse_theme("watertop = \"#FF7A00\"", "typo").unwrap_err();
+        assert!(err.contains("watertop"), "{err}");
+        assert!(parse_theme("name = \"\"", "empty").is_err());
+        assert!(parse_theme("name = ", "broken").is_err());
+    }
+
+    #[test]
+    fn theme_names_cannot_escape_skin_dir() {
+        let evil = file_stem("../../evil");
+        assert!(evil.starts_with("______evil-"), "{evil}");
+        assert!(!evil.contains(['/', '.']), "{evil}");
+        assert_eq!(file_stem("霓虹_夜"), "霓虹_夜");
+        assert_ne!(file_stem("霓虹 夜"), file_stem("霓虹_夜"));
+        assert_eq!(file_stem("霓虹 夜"), file_stem(" 霓虹 夜 "));
+        assert_eq!(file_stem("  "), "theme");
+    }
+
+    #[test]
+    fn scales_theme_opacity() {
+        let theme = BallTheme::default().with_opacity(0.5);
+        assert_eq!(theme.text.0.a, BallTheme::default().text.0.a * 0.5);
+        assert_eq!(BallTheme::default().with_opacity(1.0), BallTheme::default());
+    }
+}
@@ -0,0 +1,118 @@
+use iced::widget::canvas::{Canvas, Frame, Geometry, Path, Program, Stroke};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Theme, mouse};
+
+/// 迷你走势图的时间窗口。
+pub const SPARKLINE_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+const LINE_COLOR: Color = Color::from_rgb(0.0, 0.85, 0.7);
+
+/// 剩余额度走势：横轴为 `[start, end]` 的时间，纵轴为窗口内的最小值到最大值。
+struct Sparkline {
+    points: Vec<Point>,
+}
+
+pub fn sparkline<'a, Message: 'a>(
+    samples: &[(u64, f64)],
+    start: u64,
+    end: u64,
+    height: f32,
+) -> Element<'a, Message> {
+    Canvas::new(Sparkline {
+        points: normalize(samples, start, end),
+    })
+    .width(Length::Fill)
+    .height(Length::Fixed(height))
+    .into()
+}
+
+/// 把采样映射到单位正方形（x 向右、y 向下）；值都相同时画在中线上。
+fn normalize(samples: &[(u64, f64)], start: u64, end: u64) -> Vec<Point> {
+    let samples: Vec<&(u64, f64)> = samples
+        .iter()
+        .filter(|(at, value)| (start..=end).contains(at) && value.is_finite())
+        .collect();
+    let span = end.saturating_sub(start).max(1) as f64;
+    let (min, max) = samples.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), (_, value)| (min.min(*value), max.max(*value)),
+    );
+
+    samples
+        .iter()
+        .map(|(at, value)| {
+            let x = (at - start) as f64 / span;
+            let y = if max - min > f64::EPSILON {
+                1.0 - (value - min) / (max - min)
+            } else {
+                0.5
+            };
+            Point::new(x as f32, y as f32)
+        })
+        .collect()
+}
+
+impl<Message> Program<Message> for Sparkline {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let size = bounds.size();
+        let mut frame = Frame::new(renderer, size);
+        frame.fill_rectangle(
+            Point::ORIGIN,
+            size,
+            Color::from_rgba8(0, 255, 200, 12.0 / 255.0),
+        );
+
+        // 上下留出线宽，避免最高/最低点被裁掉一半
+        let inset = 2.0;
+        let to_frame = |point: &Point| {
+            Point::new(
+                point.x * size.width,
+                inset + point.y * (size.height - inset * 2.0),
+            )
+        };
+        match self.points.as_slice() {
+            [] => {}
+            [only] => frame.fill(&Path::circle(to_frame(only), 2.0), LINE_COLOR),
+            [first, rest @ ..] => {
+                let line = Path::new(|builder| {
+                    builder.move_to(to_frame(first));
+                    for point in rest {
+                        builder.line_to(to_frame(point));
+                    }
+                });
+                frame.stroke(
+                    &line,
+                    Stroke::default().with_width(1.5).with_color(LINE_COLOR),
+                );
+            }
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Point;
+
+    use super::normalize;
+
+    #[test]
+    fn maps_samples_into_unit_square() {
+        let points = normalize(&[(0, 5.0), (100, 10.0), (150, 0.0), (300, 1.0)], 100, 200);
+        assert_eq!(points, [Point::new(0.0, 0.0), Point::new(0.5, 1.0)]);
+    }
+
+    #[test]
+    fn flat_series_sits_on_midline() {
+        let points = normalize(&[(10, 3.0), (20, 3.0)], 0, 20);
+        assert_eq!(points, [Point::new(0.5, 0.5), Point::new(1.0, 0.5)]);
+    }
+}
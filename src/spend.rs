@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::schedule::BillingPeriod;
+
+/// 按单价折算的本期花费与月底预估。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpendEstimate {
+    pub spent: f64,
+    pub projected: f64,
+}
+
+impl SpendEstimate {
+    /// `daily` 为按日用量；未设置账单日时按自然月估算，单价为 0 时不估算。
+    pub fn compute(
+        daily: &BTreeMap<NaiveDate, f64>,
+        unit_price: f64,
+        billing_day: u32,
+        today: NaiveDate,
+    ) -> Option<Self> {
+        if !unit_price.is_finite() || unit_price <= 0.0 {
+            return None;
+        }
+
+        let period = BillingPeriod::containing(today, billing_day)
+            .or_else(|| BillingPeriod::containing(today, 1))?;
+        let used: f64 = daily
+            .range(period.start..=today)
+            .map(|(_, used)| used)
+            .sum();
+        let spent = used * unit_price;
+        let projected = spent / period.day_number(today) as f64 * period.length_days() as f64;
+
+        Some(Self { spent, projected })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::NaiveDate;
+
+    use super::SpendEstimate;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn projects_calendar_month_spend() {
+        let daily: BTreeMap<NaiveDate, f64> = (1..=10).map(|day| (date(4, day), 2.0)).collect();
+        let estimate = SpendEstimate::compute(&daily, 0.5, 0, date(4, 10)).unwrap();
+        assert_eq!(estimate.spent, 10.0);
+        // 4 月共 30 天
+        assert_eq!(estimate.projected, 30.0);
+    }
+
+    #[test]
+    fn only_counts_current_billing_period() {
+        let daily = BTreeMap::from([(date(4, 14), 100.0), (date(4, 15), 4.0)]);
+        let estimate = SpendEstimate::compute(&daily, 1.0, 15, date(4, 15)).unwrap();
+        assert_eq!(estimate.spent, 4.0);
+    }
+
+    #[test]
+    fn disabled_without_unit_price() {
+        assert_eq!(
+            SpendEstimate::compute(&BTreeMap::new(), 0.0, 0, date(4, 1)),
+            None
+        );
+    }
+}
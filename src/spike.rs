@@ -0,0 +1,104 @@
+/// 用作基线的最近间隔数。
+const BASELINE: usize = 48;
+/// 基线间隔不足时不判断，避免刚开始使用时误报。
+const MIN_BASELINE: usize = 12;
+/// 超出基线均值的标准差倍数。
+const SIGMA: f64 = 3.0;
+/// 模拟倒计时取最近几个间隔的平均速度，减少单次波动。
+const RECENT: usize = 3;
+/// 间隔太短（如连续手动刷新）时并入下一个间隔，避免一次请求就算出极高的速度。
+const MIN_INTERVAL_SECONDS: u64 = 30;
+
+/// 消耗速度异常：当前速度与基线均值（额度/小时）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spike {
+    pub rate: f64,
+    pub mean: f64,
+}
+
+/// 由按时间排序的采样（时间, 剩余额度）算出各间隔的消耗速度（额度/小时），额度上升记为 0。
+pub fn burn_rates(samples: &[(u64, f64)]) -> Vec<f64> {
+    let mut rates = Vec::new();
+    let Some(mut anchor) = samples.first().copied() else {
+        return rates;
+    };
+    for &(at, remaining) in &samples[1..] {
+        let seconds = at.saturating_sub(anchor.0);
+        if seconds < MIN_INTERVAL_SECONDS {
+            continue;
+        }
+        let used = (anchor.1 - remaining).max(0.0);
+        rates.push(used * 3600.0 / seconds as f64);
+        anchor = (at, remaining);
+    }
+    rates
+}
+
+/// 最近几个间隔的平均消耗速度（额度/小时），没有足够采样时为 `None`。
+pub fn recent_rate(samples: &[(u64, f64)]) -> Option<f64> {
+    let rates = burn_rates(samples);
+    let recent = &rates[rates.len().saturating_sub(RECENT)..];
+    (!recent.is_empty()).then(|| recent.iter().sum::<f64>() / recent.len() as f64)
+}
+
+/// 最新间隔的速度超过之前若干间隔的均值 + k·σ，且至少是均值的两倍时视为异常。
+pub fn detect(rates: &[f64]) -> Option<Spike> {
+    let (&rate, history) = rates.split_last()?;
+    let baseline = &history[history.len().saturating_sub(BASELINE)..];
+    if baseline.len() < MIN_BASELINE || rate <= 0.0 {
+        return None;
+    }
+
+    let n = baseline.len() as f64;
+    let mean = baseline.iter().sum::<f64>() / n;
+    let variance = baseline.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+    let threshold = (mean + SIGMA * variance.sqrt()).max(mean * 2.0);
+    (rate > threshold).then_some(Spike { rate, mean })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{burn_rates, detect, recent_rate};
+
+    #[test]
+    fn derives_hourly_burn_rates() {
+        let samples = [
+            (0, 100.0),
+            (1800, 99.0),
+            (1810, 90.0),
+            (3600, 80.0),
+            (7200, 95.0),
+        ];
+        assert_eq!(burn_rates(&samples), [2.0, 38.0, 0.0]);
+    }
+
+    #[test]
+    fn averages_the_most_recent_rates() {
+        let samples = [
+            (0, 100.0),
+            (3600, 99.0),
+            (7200, 97.0),
+            (10800, 94.0),
+            (14400, 90.0),
+        ];
+        assert_eq!(recent_rate(&samples), Some(3.0));
+        assert_eq!(recent_rate(&samples[..1]), None);
+    }
+
+    #[test]
+    fn flags_rates_far_above_the_baseline() {
+        let mut rates: Vec<f64> = (0..20).map(|i| 1.0 + f64::from(i % 3) * 0.5).collect();
+        assert_eq!(detect(&rates), None);
+
+        rates.push(2.4);
+        assert_eq!(detect(&rates), None);
+
+        rates.push(9.0);
+        let spike = detect(&rates).unwrap();
+        assert_eq!(spike.rate, 9.0);
+        assert!((spike.mean - 1.5).abs() < 0.1);
+
+        // 基线太短不判断
+        assert_eq!(detect(&[1.0, 1.0, 50.0]), None);
+    }
+}
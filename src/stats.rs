@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+
+/// 仅保存在本机的运行统计，不会上传到任何地方。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    #[serde(default)]
+    pub fetch_success: u64,
+    #[serde(default)]
+    pub fetch_failure: u64,
+    #[serde(default)]
+    pub total_latency_ms: u64,
+    #[serde(default)]
+    pub running_seconds: u64,
+    /// 统计起始时间（Unix 秒），重置时更新。
+    #[serde(default)]
+    pub since: Option<u64>,
+}
+
+impl UsageStats {
+    pub fn fresh() -> Self {
+        Self {
+            since: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            ..Self::default()
+        }
+    }
+
+    pub fn record_fetch(&mut self, success: bool, latency: Duration) {
+        if success {
+            self.fetch_success += 1;
+        } else {
+            self.fetch_failure += 1;
+        }
+        self.total_latency_ms += latency.as_millis() as u64;
+    }
+
+    pub fn add_running(&mut self, elapsed: Duration) {
+        self.running_seconds += elapsed.as_secs();
+    }
+
+    pub fn total_fetches(&self) -> u64 {
+        self.fetch_success + self.fetch_failure
+    }
+
+    pub fn average_latency_ms(&self) -> Option<u64> {
+        let total = self.total_fetches();
+        (total > 0).then(|| self.total_latency_ms / total)
+    }
+
+    pub fn success_rate(&self) -> Option<f32> {
+        let total = self.total_fetches();
+        (total > 0).then(|| self.fetch_success as f32 / total as f32)
+    }
+
+    pub fn running_hours(&self) -> f32 {
+        self.running_seconds as f32 / 3600.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsStore {
+    pub path: PathBuf,
+}
+
+impl StatsStore {
+    pub fn load(&self) -> Result<UsageStats, ConfigError> {
+        if !self.path.exists() {
+            return Ok(UsageStats::fresh());
+        }
+        let raw = std::fs::read_to_string(&self.path)?;
+        Ok(toml::from_str::<UsageStats>(&raw)?)
+    }
+
+    pub fn save(&self, stats: &UsageStats) -> Result<(), ConfigError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let raw = toml::to_string_pretty(stats)?;
+        std::fs::write(&self.path, raw)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::UsageStats;
+
+    #[test]
+    fn empty_stats_have_no_averages() {
+        let stats = UsageStats::default();
+        assert_eq!(stats.average_latency_ms(), None);
+        assert_eq!(stats.success_rate(), None);
+    }
+
+    #[test]
+    fn records_fetch_outcomes() {
+        let mut stats = UsageStats::default();
+        stats.record_fetch(true, Duration::from_millis(300));
+        stats.record_fetch(true, Duration::from_millis(500));
+        stats.record_fetch(false, Duration::from_millis(1000));
+
+        assert_eq!(stats.total_fetches(), 3);
+        assert_eq!(stats.average_latency_ms(), Some(600));
+        assert_eq!(stats.success_rate(), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn accumulates_running_time() {
+        let mut stats = UsageStats::default();
+        stats.add_running(Duration::from_secs(1800));
+        stats.add_running(Duration::from_secs(1800));
+        assert_eq!(stats.running_hours(), 1.0);
+    }
+}
@@ -0,0 +1,120 @@
+//! 悬浮球文字排版：估算宽度、中间省略与从右到左文字的识别。
+//!
+//! 画布没有现成的测量接口，这里按字符类别估算宽度（以字号为单位），结果由调用方缓存。
+
+const ELLIPSIS: char = '…';
+
+/// 一段文字的估算宽度（字号的倍数）与书写方向。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMetrics {
+    pub width: f32,
+    pub rtl: bool,
+}
+
+impl TextMetrics {
+    pub fn of(text: &str) -> Self {
+        Self {
+            width: measure(text),
+            rtl: is_rtl(text),
+        }
+    }
+}
+
+/// 全角字符（中日韩文字、全角符号、emoji）约为 1 个字号宽，其余按常见无衬线字体估算。
+fn char_width(c: char) -> f32 {
+    match u32::from(c) {
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1FAFF
+        | 0x20000..=0x3FFFD => 1.0,
+        _ if c == ELLIPSIS => 1.0,
+        _ if c.is_whitespace() => 0.3,
+        _ if c.is_ascii_uppercase() || c.is_ascii_digit() => 0.62,
+        _ => 0.55,
+    }
+}
+
+pub fn measure(text: &str) -> f32 {
+    text.chars().map(char_width).sum()
+}
+
+/// 超出 `max_width` 时保留首尾、中间替换为省略号，便于区分名称相近的订阅。
+pub fn middle_ellipsis(text: &str, max_width: f32) -> String {
+    if measure(text) <= max_width {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let budget = max_width - char_width(ELLIPSIS);
+    let (mut head, mut tail) = (0, chars.len());
+    let (mut head_width, mut tail_width) = (0.0, 0.0);
+    while head < tail {
+        // 优先补较短的一侧，首尾长度大致相当
+        let (c, from_head) = if head_width <= tail_width {
+            (chars[head], true)
+        } else {
+            (chars[tail - 1], false)
+        };
+        if head_width + tail_width + char_width(c) > budget {
+            break;
+        }
+        if from_head {
+            head += 1;
+            head_width += char_width(c);
+        } else {
+            tail -= 1;
+            tail_width += char_width(c);
+        }
+    }
+
+    let mut fitted: String = chars[..head].iter().collect();
+    fitted.push(ELLIPSIS);
+    fitted.extend(&chars[tail..]);
+    fitted
+}
+
+/// 按第一个有方向的字符判断：希伯来文、阿拉伯文等为从右到左。
+pub fn is_rtl(text: &str) -> bool {
+    text.chars()
+        .find_map(|c| match u32::from(c) {
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF => Some(true),
+            _ if c.is_alphabetic() => Some(false),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextMetrics, is_rtl, measure, middle_ellipsis};
+
+    #[test]
+    fn measures_full_width_characters_wider() {
+        assert_eq!(measure("套餐"), 2.0);
+        assert!(measure("Pro") < measure("专业版"));
+    }
+
+    #[test]
+    fn keeps_both_ends_when_shortening() {
+        assert_eq!(middle_ellipsis("Pro", 5.0), "Pro");
+        assert_eq!(middle_ellipsis("企业版套餐月付年度", 5.0), "企业…年度");
+
+        let fitted = middle_ellipsis("team-subscription-2024", 6.0);
+        assert!(fitted.starts_with("team"));
+        assert!(fitted.ends_with("2024"));
+        assert!(measure(&fitted) <= 6.0);
+    }
+
+    #[test]
+    fn detects_right_to_left_text() {
+        assert!(is_rtl("123 اشتراك"));
+        assert!(is_rtl("מנוי Pro"));
+        assert!(!is_rtl("Pro اشتراك"));
+        assert!(!TextMetrics::of("套餐").rtl);
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+/// 同时显示的提示条数上限，超出时丢弃最早的。
+const MAX_TOASTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+impl ToastKind {
+    /// 错误停留更久，便于看清。
+    fn duration(self) -> Duration {
+        match self {
+            ToastKind::Info | ToastKind::Success => Duration::from_millis(2500),
+            ToastKind::Error => Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+    until: Instant,
+}
+
+/// 设置页底部的提示条，自动消失，也可点击关闭。
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    next_id: u64,
+    items: Vec<Toast>,
+}
+
+impl Toasts {
+    /// 与最新一条内容相同时只延长显示时间，避免连续操作刷屏。
+    pub fn push(&mut self, kind: ToastKind, message: impl Into<String>, now: Instant) {
+        let message = message.into();
+        let until = now + kind.duration();
+        if let Some(last) = self
+            .items
+            .last_mut()
+            .filter(|last| last.kind == kind && last.message == message)
+        {
+            last.until = until;
+            return;
+        }
+
+        if self.items.len() == MAX_TOASTS {
+            self.items.remove(0);
+        }
+        self.next_id += 1;
+        self.items.push(Toast {
+            id: self.next_id,
+            kind,
+            message,
+            until,
+        });
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.retain(|toast| toast.id != id);
+    }
+
+    pub fn expire(&mut self, now: Instant) {
+        self.items.retain(|toast| now < toast.until);
+    }
+
+    pub fn items(&self) -> &[Toast] {
+        &self.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{ToastKind, Toasts};
+
+    #[test]
+    fn expires_merges_and_caps_toasts() {
+        let now = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push(ToastKind::Success, "已保存", now);
+        toasts.push(ToastKind::Success, "已保存", now + Duration::from_secs(2));
+        toasts.push(ToastKind::Error, "刷新失败", now);
+        assert_eq!(toasts.items().len(), 2);
+
+        toasts.expire(now + Duration::from_secs(3));
+        assert_eq!(toasts.items().len(), 2);
+        toasts.expire(now + Duration::from_millis(4500));
+        assert_eq!(toasts.items().len(), 1);
+        assert_eq!(toasts.items()[0].message, "刷新失败");
+
+        for message in ["a", "b", "c"] {
+            toasts.push(ToastKind::Info, message, now);
+        }
+        let messages: Vec<_> = toasts.items().iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, ["a", "b", "c"]);
+
+        let id = toasts.items()[1].id;
+        toasts.dismiss(id);
+        assert_eq!(toasts.items().len(), 2);
+    }
+}
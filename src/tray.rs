@@ -1,69 +1,479 @@
-use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
-use tray_icon::{Icon, TrayIconBuilder};
+use tray_icon::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tray_icon::{Icon, MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+
+use crate::bus::{self, Command};
+use crate::config::TrayClickAction;
+use crate::platform;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrayAction {
     Refresh,
+    CopyValue,
+    ToggleQr,
+    TogglePairingQr,
+    ToggleAutostart,
+    /// 切换自动刷新间隔（秒），0 表示手动刷新。
+    SetRefreshInterval(u64),
+    /// 应用第 n 个布局预设。
+    ApplyLayout(usize),
+    ToggleBall,
+    ToggleFollowCursor,
+    TogglePrivacy,
+    /// 把悬浮球移回主显示器，找不到悬浮球时使用。
+    ResetPosition,
+    ExportScreenshot,
+    RecordAnimation,
+    Notifications,
     Settings,
+    /// 清除凭证，需要在设置页确认。
+    WipeCredentials,
+    About,
     Exit,
 }
 
+/// 任务栏（菜单栏）的明暗，浅色背景上改用深色图标与白色数字以保持可见。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayTheme {
+    Dark,
+    Light,
+}
+
+impl TrayTheme {
+    pub fn detect() -> Self {
+        if platform::light_taskbar() {
+            TrayTheme::Light
+        } else {
+            TrayTheme::Dark
+        }
+    }
+
+    fn palette(self) -> IconPalette {
+        match self {
+            TrayTheme::Dark => IconPalette {
+                rim: [0, 255, 170],
+                outer: [0.0, 200.0, 180.0],
+                inner: [30.0, 255.0, 220.0],
+                digits: [5, 20, 35, 255],
+            },
+            TrayTheme::Light => IconPalette {
+                rim: [0, 110, 95],
+                outer: [0.0, 130.0, 115.0],
+                inner: [0.0, 165.0, 140.0],
+                digits: [255, 255, 255, 255],
+            },
+        }
+    }
+}
+
+/// 图标配色：边框、由外到内渐变的填充与数字颜色。
+struct IconPalette {
+    rim: [u8; 3],
+    outer: [f32; 3],
+    inner: [f32; 3],
+    digits: [u8; 4],
+}
+
 pub struct Tray {
-    _tray_icon: tray_icon::TrayIcon,
+    tray_icon: tray_icon::TrayIcon,
+    tooltip: String,
+    percent: Option<u8>,
+    theme: TrayTheme,
+    /// 文字模式下显示的标题，此时不显示图标。
+    title: Option<String>,
+    autostart_item: CheckMenuItem,
+    follow_item: CheckMenuItem,
+    privacy_item: CheckMenuItem,
+    interval_items: Vec<(u64, CheckMenuItem)>,
+    layout_menu: Submenu,
+    layout_items: Vec<MenuItem>,
 }
 
+const DEFAULT_TOOLTIP: &str = "RightCode Floating Ball";
+
 const MENU_ID_REFRESH: &str = "refresh";
+const MENU_ID_COPY_VALUE: &str = "copy_value";
+const MENU_ID_QR: &str = "qr";
+const MENU_ID_PAIRING_QR: &str = "pairing_qr";
+const MENU_ID_AUTOSTART: &str = "autostart";
+const MENU_ID_FOLLOW: &str = "follow_cursor";
+const MENU_ID_PRIVACY: &str = "privacy";
+const MENU_ID_RESET_POSITION: &str = "reset_position";
+const MENU_ID_INTERVAL_PREFIX: &str = "refresh_interval:";
+const MENU_ID_LAYOUT_PREFIX: &str = "layout:";
+const MENU_ID_SCREENSHOT: &str = "screenshot";
+const MENU_ID_RECORD: &str = "record";
+const MENU_ID_NOTIFICATIONS: &str = "notifications";
 const MENU_ID_SETTINGS: &str = "settings";
+const MENU_ID_WIPE: &str = "wipe_credentials";
+const MENU_ID_ABOUT: &str = "about";
 const MENU_ID_EXIT: &str = "exit";
 
+const ICON_SIZE: u32 = 32;
+
+/// 只有 macOS 的状态项能只显示文字（按钮标题），其他平台仍显示图标。
+pub const MENU_BAR_TEXT_SUPPORTED: bool = cfg!(target_os = "macos");
+
+/// macOS 菜单栏图标使用模板图：只有透明度，颜色由系统按菜单栏明暗着色。
+const TEMPLATE_ICON: bool = cfg!(target_os = "macos");
+
+/// 托盘「刷新频率」子菜单的预设（秒），0 表示手动。
+const REFRESH_PRESETS: [(u64, &str); 4] =
+    [(30, "30 秒"), (60, "1 分钟"), (300, "5 分钟"), (0, "手动")];
+
+/// 3x5 点阵数字，每行低 3 位从左到右。
+const DIGIT_GLYPHS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
 impl Tray {
     pub fn new() -> Result<Self, String> {
         let menu = Menu::new();
         menu.append(&MenuItem::with_id(MENU_ID_REFRESH, "刷新", true, None))
             .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(
+            MENU_ID_COPY_VALUE,
+            "复制余额",
+            true,
+            None,
+        ))
+        .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(MENU_ID_QR, "手机查看", true, None))
+            .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(
+            MENU_ID_PAIRING_QR,
+            "远程配对",
+            true,
+            None,
+        ))
+        .map_err(|e| e.to_string())?;
+        let interval_menu = Submenu::new("刷新频率", true);
+        let mut interval_items = Vec::with_capacity(REFRESH_PRESETS.len());
+        for (seconds, label) in REFRESH_PRESETS {
+            let item = CheckMenuItem::with_id(
+                format!("{MENU_ID_INTERVAL_PREFIX}{seconds}"),
+                label,
+                true,
+                false,
+                None,
+            );
+            interval_menu.append(&item).map_err(|e| e.to_string())?;
+            interval_items.push((seconds, item));
+        }
+        menu.append(&interval_menu).map_err(|e| e.to_string())?;
+        let layout_menu = Submenu::new("布局", true);
+        menu.append(&layout_menu).map_err(|e| e.to_string())?;
+        let follow_item = CheckMenuItem::with_id(MENU_ID_FOLLOW, "跟随鼠标", true, false, None);
+        menu.append(&follow_item).map_err(|e| e.to_string())?;
+        let privacy_item = CheckMenuItem::with_id(MENU_ID_PRIVACY, "隐私模式", true, false, None);
+        menu.append(&privacy_item).map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(
+            MENU_ID_RESET_POSITION,
+            "重置位置",
+            true,
+            None,
+        ))
+        .map_err(|e| e.to_string())?;
+        let autostart_item =
+            CheckMenuItem::with_id(MENU_ID_AUTOSTART, "开机自启", false, false, None);
+        menu.append(&autostart_item).map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(
+            MENU_ID_SCREENSHOT,
+            "导出截图",
+            true,
+            None,
+        ))
+        .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(MENU_ID_RECORD, "录制动图", true, None))
+            .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(
+            MENU_ID_NOTIFICATIONS,
+            "通知历史",
+            true,
+            None,
+        ))
+        .map_err(|e| e.to_string())?;
         menu.append(&MenuItem::with_id(MENU_ID_SETTINGS, "设置", true, None))
             .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(MENU_ID_WIPE, "清除凭证", true, None))
+            .map_err(|e| e.to_string())?;
+        menu.append(&MenuItem::with_id(MENU_ID_ABOUT, "关于", true, None))
+            .map_err(|e| e.to_string())?;
         menu.append(&PredefinedMenuItem::separator())
             .map_err(|e| e.to_string())?;
         menu.append(&MenuItem::with_id(MENU_ID_EXIT, "退出", true, None))
             .map_err(|e| e.to_string())?;
 
-        let icon = default_tray_icon().map_err(|e| format!("tray icon error: {e}"))?;
+        let theme = TrayTheme::detect();
+        let icon = Icon::from_rgba(icon_rgba(None, theme), ICON_SIZE, ICON_SIZE)
+            .map_err(|e| format!("tray icon error: {e}"))?;
 
         let tray_icon = TrayIconBuilder::new()
-            .with_tooltip("RightCode Floating Ball")
+            .with_tooltip(DEFAULT_TOOLTIP)
             .with_menu(Box::new(menu))
             .with_icon(icon)
+            .with_icon_as_template(TEMPLATE_ICON)
             .build()
             .map_err(|e| e.to_string())?;
+        install_event_handlers();
 
         Ok(Self {
-            _tray_icon: tray_icon,
+            tray_icon,
+            tooltip: DEFAULT_TOOLTIP.to_string(),
+            percent: None,
+            theme,
+            title: None,
+            autostart_item,
+            follow_item,
+            privacy_item,
+            interval_items,
+            layout_menu,
+            layout_items: Vec::new(),
         })
     }
+
+    /// 按配置中的布局预设重建「布局」子菜单，没有预设时显示一个不可点的占位项。
+    pub fn set_layout_presets(&mut self, names: &[String]) {
+        for item in self.layout_items.drain(..) {
+            let _ = self.layout_menu.remove(&item);
+        }
+
+        if names.is_empty() {
+            let item = MenuItem::new("（在设置页「主题」中保存）", false, None);
+            if self.layout_menu.append(&item).is_ok() {
+                self.layout_items.push(item);
+            }
+            return;
+        }
+
+        for (index, name) in names.iter().enumerate() {
+            let item =
+                MenuItem::with_id(format!("{MENU_ID_LAYOUT_PREFIX}{index}"), name, true, None);
+            if self.layout_menu.append(&item).is_ok() {
+                self.layout_items.push(item);
+            }
+        }
+    }
+
+    /// 同步「开机自启」勾选状态；点击菜单时系统会先自行切换勾选，需以实际状态为准覆盖。
+    pub fn set_autostart(&self, checked: bool, enabled: bool) {
+        self.autostart_item.set_checked(checked);
+        self.autostart_item.set_enabled(enabled);
+    }
+
+    pub fn set_follow_cursor(&self, checked: bool) {
+        self.follow_item.set_checked(checked);
+    }
+
+    pub fn set_privacy(&self, checked: bool) {
+        self.privacy_item.set_checked(checked);
+    }
+
+    /// 勾选与当前间隔一致的预设；自定义间隔时全部不勾选。
+    pub fn set_refresh_interval(&self, seconds: u64, enabled: bool) {
+        for (preset, item) in &self.interval_items {
+            item.set_checked(*preset == seconds);
+            item.set_enabled(enabled);
+        }
+    }
+
+    /// 在托盘图标上绘制剩余百分比，`None` 恢复默认图标。
+    pub fn set_percent(&mut self, percent: Option<u8>) {
+        let percent = percent.map(|p| p.min(100));
+        if self.percent != percent {
+            self.apply_icon(percent, self.theme);
+        }
+    }
+
+    /// 任务栏明暗变化时按当前百分比重绘图标。
+    pub fn set_theme(&mut self, theme: TrayTheme) {
+        if self.theme != theme {
+            self.apply_icon(self.percent, theme);
+        }
+    }
+
+    /// 以文字代替图标，`None` 恢复图标；不支持的平台忽略。
+    pub fn set_title(&mut self, title: Option<&str>) {
+        let title = title.filter(|_| MENU_BAR_TEXT_SUPPORTED);
+        if self.title.as_deref() == title {
+            return;
+        }
+
+        self.tray_icon.set_title(title);
+        self.title = title.map(str::to_string);
+        if self.title.is_some() {
+            let _ = self.tray_icon.set_icon(None);
+        } else {
+            self.apply_icon(self.percent, self.theme);
+        }
+    }
+
+    fn apply_icon(&mut self, percent: Option<u8>, theme: TrayTheme) {
+        // 文字模式只记下状态，切回图标时再绘制
+        if self.title.is_some() {
+            self.percent = percent;
+            self.theme = theme;
+            return;
+        }
+        let Ok(icon) = Icon::from_rgba(icon_rgba(percent, theme), ICON_SIZE, ICON_SIZE) else {
+            return;
+        };
+        // macOS 上单独 set_icon 会丢掉模板标记，图标不再随菜单栏深浅色反转
+        #[cfg(target_os = "macos")]
+        let applied = self
+            .tray_icon
+            .set_icon_with_as_template(Some(icon), TEMPLATE_ICON);
+        #[cfg(not(target_os = "macos"))]
+        let applied = self.tray_icon.set_icon(Some(icon));
+        if applied.is_ok() {
+            self.percent = percent;
+            self.theme = theme;
+        }
+    }
+
+    pub fn set_tooltip(&mut self, tooltip: &str) {
+        if self.tooltip == tooltip {
+            return;
+        }
+
+        if self.tray_icon.set_tooltip(Some(tooltip)).is_ok() {
+            self.tooltip = tooltip.to_string();
+        }
+    }
 }
 
-pub fn drain_actions() -> Vec<TrayAction> {
-    let mut actions = Vec::new();
+/// 托盘事件：菜单项直接对应动作，图标单击/双击由应用按当前配置映射。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    Menu(TrayAction),
+    Click,
+    DoubleClick,
+}
 
-    while let Ok(event) = MenuEvent::receiver().try_recv() {
-        let action = match event.id.as_ref() {
-            MENU_ID_REFRESH => Some(TrayAction::Refresh),
-            MENU_ID_SETTINGS => Some(TrayAction::Settings),
-            MENU_ID_EXIT => Some(TrayAction::Exit),
-            _ => None,
+/// 托盘事件在系统事件循环中回调，直接转发到命令总线，不再逐帧轮询。
+fn install_event_handlers() {
+    TrayIconEvent::set_event_handler(Some(|event| {
+        let event = match event {
+            TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } => TrayEvent::Click,
+            TrayIconEvent::DoubleClick {
+                button: MouseButton::Left,
+                ..
+            } => TrayEvent::DoubleClick,
+            _ => return,
         };
+        bus::send(Command::Tray(event));
+    }));
+
+    MenuEvent::set_event_handler(Some(|event: MenuEvent| {
+        if let Some(action) = menu_action(event.id.as_ref()) {
+            bus::send(Command::Tray(TrayEvent::Menu(action)));
+        }
+    }));
+}
+
+fn menu_action(id: &str) -> Option<TrayAction> {
+    if let Some(seconds) = parse_interval_id(id) {
+        return Some(TrayAction::SetRefreshInterval(seconds));
+    }
+    if let Some(index) = parse_layout_id(id) {
+        return Some(TrayAction::ApplyLayout(index));
+    }
 
-        if let Some(action) = action {
-            actions.push(action);
+    match id {
+        MENU_ID_REFRESH => Some(TrayAction::Refresh),
+        MENU_ID_COPY_VALUE => Some(TrayAction::CopyValue),
+        MENU_ID_QR => Some(TrayAction::ToggleQr),
+        MENU_ID_PAIRING_QR => Some(TrayAction::TogglePairingQr),
+        MENU_ID_AUTOSTART => Some(TrayAction::ToggleAutostart),
+        MENU_ID_FOLLOW => Some(TrayAction::ToggleFollowCursor),
+        MENU_ID_PRIVACY => Some(TrayAction::TogglePrivacy),
+        MENU_ID_RESET_POSITION => Some(TrayAction::ResetPosition),
+        MENU_ID_SCREENSHOT => Some(TrayAction::ExportScreenshot),
+        MENU_ID_RECORD => Some(TrayAction::RecordAnimation),
+        MENU_ID_NOTIFICATIONS => Some(TrayAction::Notifications),
+        MENU_ID_SETTINGS => Some(TrayAction::Settings),
+        MENU_ID_WIPE => Some(TrayAction::WipeCredentials),
+        MENU_ID_ABOUT => Some(TrayAction::About),
+        MENU_ID_EXIT => Some(TrayAction::Exit),
+        _ => None,
+    }
+}
+
+fn parse_interval_id(id: &str) -> Option<u64> {
+    id.strip_prefix(MENU_ID_INTERVAL_PREFIX)?.parse().ok()
+}
+
+fn parse_layout_id(id: &str) -> Option<usize> {
+    id.strip_prefix(MENU_ID_LAYOUT_PREFIX)?.parse().ok()
+}
+
+pub fn click_action(action: TrayClickAction) -> Option<TrayAction> {
+    match action {
+        TrayClickAction::None => None,
+        TrayClickAction::ToggleBall => Some(TrayAction::ToggleBall),
+        TrayClickAction::Settings => Some(TrayAction::Settings),
+        TrayClickAction::Refresh => Some(TrayAction::Refresh),
+    }
+}
+
+/// 模板图由系统着色，与任务栏明暗无关。
+fn icon_rgba(percent: Option<u8>, theme: TrayTheme) -> Vec<u8> {
+    if TEMPLATE_ICON {
+        render_template_rgba(percent)
+    } else {
+        render_icon_rgba(percent, theme)
+    }
+}
+
+/// 单色模板图：有百分比时为实心圆、数字镂空；没有时为圆环加半透明内圈。
+fn render_template_rgba(percent: Option<u8>) -> Vec<u8> {
+    let size = ICON_SIZE;
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+
+    let center = (size as f32 - 1.0) / 2.0;
+    let radius = (size as f32 / 2.0) - 1.0;
+    let border = 2.0;
+
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f32 - center;
+            let dy = y as f32 - center;
+            let d = (dx * dx + dy * dy).sqrt();
+            if d > radius {
+                continue;
+            }
+
+            let alpha = if d >= radius - border || percent.is_some() {
+                255
+            } else {
+                70
+            };
+            rgba[((y * size + x) * 4 + 3) as usize] = alpha;
         }
     }
 
-    actions
+    if let Some(percent) = percent {
+        draw_number(&mut rgba, size, percent, [0, 0, 0, 0]);
+    }
+
+    rgba
 }
 
-fn default_tray_icon() -> Result<Icon, tray_icon::BadIcon> {
-    let size = 32u32;
+fn render_icon_rgba(percent: Option<u8>, theme: TrayTheme) -> Vec<u8> {
+    let palette = theme.palette();
+    let size = ICON_SIZE;
     let mut rgba = vec![0u8; (size * size * 4) as usize];
 
     let center = (size as f32 - 1.0) / 2.0;
@@ -82,23 +492,143 @@ fn default_tray_icon() -> Result<Icon, tray_icon::BadIcon> {
 
             let t = (1.0 - (d / radius)).clamp(0.0, 1.0);
 
-            let fill_r = (0.0 * (1.0 - t) + 30.0 * t) as u8;
-            let fill_g = (200.0 * (1.0 - t) + 255.0 * t) as u8;
-            let fill_b = (180.0 * (1.0 - t) + 220.0 * t) as u8;
-
-            let (r, g, b, a) = if d >= radius - border {
-                (0, 255, 170, 255)
+            let [r, g, b] = if d >= radius - border {
+                palette.rim
             } else {
-                (fill_r, fill_g, fill_b, 255)
+                std::array::from_fn(|i| (palette.outer[i] * (1.0 - t) + palette.inner[i] * t) as u8)
             };
 
             let idx = ((y * size + x) * 4) as usize;
-            rgba[idx] = r;
-            rgba[idx + 1] = g;
-            rgba[idx + 2] = b;
-            rgba[idx + 3] = a;
+            rgba[idx..idx + 4].copy_from_slice(&[r, g, b, 255]);
         }
     }
 
-    Icon::from_rgba(rgba, size, size)
+    if let Some(percent) = percent {
+        draw_number(&mut rgba, size, percent, palette.digits);
+    }
+
+    rgba
+}
+
+/// 居中绘制数字；两位数放大 3 倍，三位数（100）放大 2 倍以免超出圆形。
+fn draw_number(rgba: &mut [u8], size: u32, value: u8, color: [u8; 4]) {
+    let digits: Vec<usize> = value
+        .to_string()
+        .bytes()
+        .map(|b| (b - b'0') as usize)
+        .collect();
+
+    let scale = if digits.len() <= 2 { 3 } else { 2 };
+    let glyph_width = 3 * scale;
+    let glyph_height = 5 * scale;
+    let gap = scale;
+    let text_width = digits.len() as u32 * glyph_width + (digits.len() as u32 - 1) * gap;
+
+    let left = (size - text_width) / 2;
+    let top = (size - glyph_height) / 2;
+
+    for (i, digit) in digits.iter().enumerate() {
+        let origin_x = left + i as u32 * (glyph_width + gap);
+        for (row, bits) in DIGIT_GLYPHS[*digit].iter().enumerate() {
+            for col in 0..3u32 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = origin_x + col * scale + dx;
+                        let y = top + row as u32 * scale + dy;
+                        let idx = ((y * size + x) * 4) as usize;
+                        rgba[idx..idx + 4].copy_from_slice(&color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ICON_SIZE, TrayAction, TrayTheme, click_action, menu_action, parse_interval_id,
+        parse_layout_id, render_icon_rgba, render_template_rgba,
+    };
+    use crate::config::TrayClickAction;
+
+    fn dark_pixels(rgba: &[u8]) -> usize {
+        rgba.chunks(4).filter(|px| **px == [5, 20, 35, 255]).count()
+    }
+
+    fn white_pixels(rgba: &[u8]) -> usize {
+        rgba.chunks(4)
+            .filter(|px| **px == [255, 255, 255, 255])
+            .count()
+    }
+
+    #[test]
+    fn default_icon_has_no_digits() {
+        let rgba = render_icon_rgba(None, TrayTheme::Dark);
+        assert_eq!(rgba.len(), (ICON_SIZE * ICON_SIZE * 4) as usize);
+        assert_eq!(dark_pixels(&rgba), 0);
+    }
+
+    #[test]
+    fn renders_percentage_digits() {
+        // "1" 有 8 个点，放大 3 倍后每个点占 9 个像素
+        assert_eq!(
+            dark_pixels(&render_icon_rgba(Some(1), TrayTheme::Dark)),
+            8 * 9
+        );
+        // 三位数也能放进图标内
+        assert!(dark_pixels(&render_icon_rgba(Some(100), TrayTheme::Dark)) > 0);
+    }
+
+    #[test]
+    fn light_taskbar_icon_is_darker_with_white_digits() {
+        let light = render_icon_rgba(Some(1), TrayTheme::Light);
+        assert_eq!(white_pixels(&light), 8 * 9);
+        assert_eq!(dark_pixels(&light), 0);
+
+        let brightness = |rgba: &[u8]| -> u32 { rgba.iter().map(|&c| u32::from(c)).sum() };
+        let blank = |theme| render_icon_rgba(None, theme);
+        assert!(brightness(&blank(TrayTheme::Light)) < brightness(&blank(TrayTheme::Dark)));
+    }
+
+    #[test]
+    fn template_icon_is_alpha_only_with_knocked_out_digits() {
+        let transparent = |rgba: &[u8]| rgba.chunks(4).filter(|px| px[3] == 0).count();
+        let blank = render_template_rgba(None);
+        let one = render_template_rgba(Some(1));
+        assert!(one.chunks(4).all(|px| px[..3] == [0, 0, 0]));
+        assert_eq!(transparent(&one) - transparent(&blank), 8 * 9);
+    }
+
+    #[test]
+    fn parses_refresh_interval_menu_ids() {
+        assert_eq!(parse_interval_id("refresh_interval:300"), Some(300));
+        assert_eq!(parse_interval_id("refresh_interval:0"), Some(0));
+        assert_eq!(parse_interval_id("refresh_interval:"), None);
+        assert_eq!(parse_interval_id("refresh"), None);
+        assert_eq!(parse_layout_id("layout:2"), Some(2));
+        assert_eq!(parse_layout_id("layout:x"), None);
+        assert_eq!(
+            menu_action("refresh_interval:60"),
+            Some(TrayAction::SetRefreshInterval(60))
+        );
+        assert_eq!(menu_action("privacy"), Some(TrayAction::TogglePrivacy));
+        assert_eq!(menu_action("unknown"), None);
+    }
+
+    #[test]
+    fn maps_click_settings_to_actions() {
+        assert_eq!(
+            click_action(TrayClickAction::ToggleBall),
+            Some(TrayAction::ToggleBall)
+        );
+        assert_eq!(
+            click_action(TrayClickAction::Settings),
+            Some(TrayAction::Settings)
+        );
+        assert_eq!(click_action(TrayClickAction::None), None);
+    }
 }
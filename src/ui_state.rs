@@ -0,0 +1,178 @@
+//! 界面状态（窗口位置、大小、选中的订阅、布局预设等）单独保存在 `<配置名>.state.json`，
+//! 配置文件只保留用户设置与凭证，便于手工编辑。
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AppConfig, ConfigError, default_opacity};
+
+/// 状态文件格式版本；旧版本（0）的状态保存在配置文件中。
+pub const STATE_VERSION: u32 = 1;
+
+/// 命名的窗口布局（如「工作」「演示」）：位置、大小、皮肤与不透明度。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutPreset {
+    pub name: String,
+    /// 窗口左上角（逻辑像素），缺省时保持当前位置。
+    #[serde(default)]
+    pub position: Option<(f32, f32)>,
+    pub size: f32,
+    #[serde(default)]
+    pub skin: String,
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default)]
+    pub version: u32,
+    /// 悬浮球窗口左上角（逻辑像素）。
+    #[serde(default)]
+    pub position: Option<(f32, f32)>,
+    #[serde(default)]
+    pub ball_size: Option<f32>,
+    /// 手动选中的订阅名，按优先级自动选择时为空。
+    #[serde(default)]
+    pub selected: Option<String>,
+    /// 手动隐藏（下班时段自动隐藏不计入）。
+    #[serde(default)]
+    pub hidden: bool,
+    /// 用户拖动调整后的设置窗口大小（宽, 高，未计入文本缩放），未调整过为空。
+    #[serde(default)]
+    pub settings_size: Option<(f32, f32)>,
+    #[serde(default)]
+    pub layout_presets: Vec<LayoutPreset>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            position: None,
+            ball_size: None,
+            selected: None,
+            hidden: false,
+            settings_size: None,
+            layout_presets: Vec::new(),
+        }
+    }
+}
+
+impl UiState {
+    /// 从旧版本保存在配置文件中的字段迁移。
+    pub fn from_legacy(config: &AppConfig) -> Self {
+        Self {
+            settings_size: config.settings_size,
+            layout_presets: config.layout_presets.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    pub path: PathBuf,
+}
+
+impl StateStore {
+    /// 状态文件不存在时返回 `None`，由调用方决定是否从配置迁移。
+    pub fn load(&self) -> Result<Option<UiState>, ConfigError> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(&self.path)?;
+        let mut state = serde_json::from_str::<UiState>(&raw)?;
+        // 较新版本写入的未知字段已被忽略，保存时按当前版本写回
+        state.version = STATE_VERSION;
+        Ok(Some(state))
+    }
+
+    pub fn save(&self, state: &UiState) -> Result<(), ConfigError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let raw = serde_json::to_string_pretty(state)?;
+        std::fs::write(&self.path, raw)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LayoutPreset, STATE_VERSION, StateStore, UiState};
+    use crate::config::AppConfig;
+
+    #[test]
+    fn round_trips_layout_presets() {
+        let state = UiState {
+            layout_presets: vec![
+                LayoutPreset {
+                    name: "工作".to_string(),
+                    position: Some((1700.0, 40.0)),
+                    size: 100.0,
+                    skin: "Sunset".to_string(),
+                    opacity: 0.8,
+                },
+                LayoutPreset {
+                    name: "演示".to_string(),
+                    position: None,
+                    size: 200.0,
+                    skin: String::new(),
+                    opacity: 1.0,
+                },
+            ],
+            ..UiState::default()
+        };
+        let raw = serde_json::to_string_pretty(&state).expect("serializable state");
+        let parsed = serde_json::from_str::<UiState>(&raw).expect("valid state");
+        assert_eq!(parsed, state);
+
+        let preset = serde_json::from_str::<LayoutPreset>(r#"{ "name": "x", "size": 80 }"#)
+            .expect("valid preset");
+        assert_eq!(preset.opacity, 1.0);
+    }
+
+    #[test]
+    fn migrates_legacy_fields_from_config() {
+        let config = toml::from_str::<AppConfig>(
+            r#"
+            settings_size = [720.0, 640.0]
+
+            [[layout_presets]]
+            name = "工作"
+            size = 120.0
+            "#,
+        )
+        .expect("valid legacy config");
+
+        let state = UiState::from_legacy(&config);
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!(state.settings_size, Some((720.0, 640.0)));
+        assert_eq!(state.layout_presets[0].name, "工作");
+
+        // 迁移后配置文件不再写出这些字段
+        let raw = toml::to_string_pretty(&config).expect("serializable config");
+        assert!(!raw.contains("layout_presets"));
+        assert!(!raw.contains("settings_size"));
+    }
+
+    #[test]
+    fn loads_missing_file_as_none_and_saves() {
+        let dir = std::env::temp_dir().join(format!("rcfb-state-{}", std::process::id()));
+        let store = StateStore {
+            path: dir.join("state.json"),
+        };
+        assert_eq!(store.load().expect("readable"), None);
+
+        let state = UiState {
+            position: Some((10.0, 20.0)),
+            selected: Some("Pro".to_string()),
+            ..UiState::default()
+        };
+        store.save(&state).expect("writable");
+        assert_eq!(store.load().expect("readable"), Some(state));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
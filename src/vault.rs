@@ -0,0 +1,95 @@
+use argon2::Argon2;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// 加密后的配置文件以此开头，据此区分明文与密文。
+const HEADER: &str = "rcfb-sealed:v1:";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// 口令最短长度，只在设置加密时检查。
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+#[derive(Debug, thiserror::Error)]
+pub enum VaultError {
+    #[error("key derivation failed")]
+    Kdf,
+    #[error("encrypt failed")]
+    Encrypt,
+    #[error("wrong passphrase or corrupted file")]
+    Decrypt,
+}
+
+pub fn is_sealed(raw: &str) -> bool {
+    raw.starts_with(HEADER)
+}
+
+/// 用 Argon2id 从口令与随机盐派生 32 字节密钥。
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, VaultError> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut_slice())
+        .map_err(|_| VaultError::Kdf)?;
+    Ok(key)
+}
+
+/// 输出 `HEADER` + base64(salt || nonce || ciphertext)，每次加密都换新的盐与 nonce。
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<String, VaultError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| VaultError::Encrypt)?;
+
+    let mut payload = salt.to_vec();
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&ciphertext);
+    Ok(format!("{HEADER}{}\n", STANDARD.encode(payload)))
+}
+
+pub fn open(passphrase: &str, sealed: &str) -> Result<Vec<u8>, VaultError> {
+    let payload = sealed
+        .trim()
+        .strip_prefix(HEADER)
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .filter(|payload| payload.len() > SALT_LEN + NONCE_LEN)
+        .ok_or(VaultError::Decrypt)?;
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| VaultError::Decrypt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VaultError, is_sealed, open, seal};
+
+    #[test]
+    fn sealed_config_round_trips_and_rejects_wrong_passphrase() {
+        let sealed = seal("correct horse", b"refresh_seconds = 60").expect("seals");
+
+        assert!(is_sealed(&sealed));
+        assert!(!is_sealed("refresh_seconds = 60"));
+        assert_eq!(
+            open("correct horse", &sealed).expect("opens"),
+            b"refresh_seconds = 60"
+        );
+        assert!(matches!(
+            open("wrong horse", &sealed),
+            Err(VaultError::Decrypt)
+        ));
+        assert!(matches!(
+            open("correct horse", "rcfb-sealed:v1:@@"),
+            Err(VaultError::Decrypt)
+        ));
+    }
+}
@@ -0,0 +1,475 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chacha20poly1305::aead::OsRng;
+use chacha20poly1305::aead::rand_core::RngCore;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+/// 读取请求头的上限，连上后不发数据的客户端不会一直占着连接。
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// 等待用户在浏览器中打开检测页的时长。
+const DETECT_UA_TIMEOUT: Duration = Duration::from_secs(120);
+
+const DETECT_UA_HTML: &str = r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<title>User-Agent 已获取</title>
+<style>
+  body { margin: 0; min-height: 100vh; display: flex; align-items: center; justify-content: center;
+         background: #050f19; color: #c8fff0; font-family: -apple-system, "Microsoft YaHei", sans-serif; }
+  .card { max-width: 640px; padding: 32px; border: 2px solid rgba(0, 255, 136, .6); border-radius: 16px; }
+  code { display: block; margin: 12px 0; color: #00ffc8; word-break: break-all; }
+  .meta { font-size: 12px; opacity: .6; }
+</style>
+</head>
+<body>
+<div class="card">
+  <div>已将此浏览器的 User-Agent 写入悬浮球配置：</div>
+  <code>{{USER_AGENT}}</code>
+  <div class="meta">请在同一浏览器中获取 cf_clearance，完成后可关闭此页。</div>
+</div>
+</body>
+</html>
+"#;
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>RightCode 额度</title>
+<style>
+  body { margin: 0; min-height: 100vh; display: flex; align-items: center; justify-content: center;
+         background: #050f19; color: #c8fff0; font-family: -apple-system, "Microsoft YaHei", sans-serif; }
+  .card { text-align: center; padding: 32px; border: 2px solid rgba(0, 255, 136, .6); border-radius: 16px; }
+  .title { font-size: 20px; opacity: .9; }
+  .value { font-size: 48px; color: #00ffc8; margin: 12px 0; }
+  .bar { height: 10px; width: 240px; background: #0c192d; border-radius: 5px; overflow: hidden; }
+  .fill { height: 100%; background: linear-gradient(90deg, #0088ff, #00ffc8); }
+  .meta { font-size: 12px; opacity: .6; margin-top: 12px; }
+  .error { color: #ff5064; }
+</style>
+</head>
+<body>
+<div class="card">
+  <div class="title" id="title">加载中…</div>
+  <div class="value" id="value">--</div>
+  <div class="bar"><div class="fill" id="fill" style="width: 0%"></div></div>
+  <div class="meta" id="meta"></div>
+</div>
+<script>
+async function refresh() {
+  try {
+    const res = await fetch("/api/status", { cache: "no-store" });
+    const s = await res.json();
+    document.getElementById("title").textContent = s.title;
+    document.getElementById("value").textContent = s.value;
+    document.getElementById("fill").style.width = (s.ratio * 100).toFixed(1) + "%";
+    const meta = document.getElementById("meta");
+    meta.className = s.error ? "meta error" : "meta";
+    meta.textContent = s.error
+      ? s.error
+      : (s.updated_at ? "更新于 " + new Date(s.updated_at * 1000).toLocaleString() : "");
+  } catch (e) {
+    document.getElementById("meta").textContent = "无法连接悬浮球";
+  }
+}
+refresh();
+setInterval(refresh, 5000);
+</script>
+</body>
+</html>
+"#;
+
+/// 访客页：只显示百分比与充足程度，不含订阅名与余额数值。
+const GUEST_HTML: &str = r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>RightCode 额度</title>
+<style>
+  body { margin: 0; min-height: 100vh; display: flex; align-items: center; justify-content: center;
+         background: #050f19; color: #c8fff0; font-family: -apple-system, "Microsoft YaHei", sans-serif; }
+  .card { text-align: center; padding: 32px; border: 2px solid rgba(0, 255, 136, .6); border-radius: 16px; }
+  .value { font-size: 48px; color: #00ffc8; margin: 12px 0; }
+  .bar { height: 10px; width: 240px; background: #0c192d; border-radius: 5px; overflow: hidden; }
+  .fill { height: 100%; background: linear-gradient(90deg, #0088ff, #00ffc8); }
+  .meta { font-size: 12px; opacity: .6; margin-top: 12px; }
+  .low { color: #ffb040; }
+  .critical { color: #ff5064; }
+</style>
+</head>
+<body>
+<div class="card">
+  <div id="level">加载中…</div>
+  <div class="value" id="value">--</div>
+  <div class="bar"><div class="fill" id="fill" style="width: 0%"></div></div>
+  <div class="meta" id="meta"></div>
+</div>
+<script>
+const api = location.pathname.replace("/guest/", "/api/guest/");
+async function refresh() {
+  try {
+    const res = await fetch(api, { cache: "no-store" });
+    const s = await res.json();
+    const level = document.getElementById("level");
+    level.textContent = s.percent >= 50 ? "额度充足" : s.percent >= 20 ? "额度偏低" : "额度紧张";
+    level.className = s.percent >= 50 ? "" : s.percent >= 20 ? "low" : "critical";
+    document.getElementById("value").textContent = s.percent + "%";
+    document.getElementById("fill").style.width = s.percent + "%";
+    document.getElementById("meta").textContent = !s.ok
+      ? "数据暂时无法更新"
+      : (s.updated_at ? "更新于 " + new Date(s.updated_at * 1000).toLocaleString() : "");
+  } catch (e) {
+    document.getElementById("meta").textContent = "无法连接悬浮球";
+  }
+}
+refresh();
+setInterval(refresh, 5000);
+</script>
+</body>
+</html>
+"#;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatusSnapshot {
+    pub title: String,
+    pub value: String,
+    pub remaining: Option<f64>,
+    pub total: Option<f64>,
+    pub unit: Option<String>,
+    pub ratio: f32,
+    pub updated_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// 访客接口返回的内容。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct GuestSnapshot {
+    percent: u8,
+    updated_at: Option<u64>,
+    ok: bool,
+}
+
+impl GuestSnapshot {
+    fn from_status(status: &StatusSnapshot) -> Self {
+        Self {
+            percent: (status.ratio.clamp(0.0, 1.0) * 100.0).round() as u8,
+            updated_at: status.updated_at,
+            ok: status.error.is_none(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Index,
+    Status,
+    GuestIndex,
+    GuestStatus,
+    Forbidden,
+    NotFound,
+}
+
+/// 访客链接 `/guest/<token>` 与接口 `/api/guest/<token>`，令牌为空时不开放。
+/// 开放访客链接后，完整页面与接口只允许本机访问，避免局域网内绕过令牌看到余额。
+fn route(path: &str, guest_token: &str, from_loopback: bool) -> Route {
+    let guest =
+        |prefix: &str| !guest_token.is_empty() && path.strip_prefix(prefix) == Some(guest_token);
+    let owner = guest_token.is_empty() || from_loopback;
+    match path {
+        "/" | "/index.html" | "/api/status" if !owner => Route::Forbidden,
+        "/" | "/index.html" => Route::Index,
+        "/api/status" => Route::Status,
+        _ if guest("/guest/") => Route::GuestIndex,
+        _ if guest("/api/guest/") => Route::GuestStatus,
+        _ => Route::NotFound,
+    }
+}
+
+/// 随机生成访客链接中的令牌（URL 安全）。
+pub fn generate_guest_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 悬浮球与网页服务之间共享的最新状态快照。
+#[derive(Debug, Clone, Default)]
+pub struct SharedStatus(Arc<RwLock<StatusSnapshot>>);
+
+impl SharedStatus {
+    pub fn set(&self, snapshot: StatusSnapshot) {
+        if let Ok(mut guard) = self.0.write() {
+            *guard = snapshot;
+        }
+    }
+
+    pub fn get(&self) -> StatusSnapshot {
+        self.0.read().map(|guard| guard.clone()).unwrap_or_default()
+    }
+}
+
+pub async fn serve(bind: String, status: SharedStatus, guest_token: String) -> Result<(), String> {
+    let listener = TcpListener::bind(&bind)
+        .await
+        .map_err(|e| format!("web: bind {bind} failed: {e}"))?;
+
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("web: accept failed: {e}"))?;
+
+        let status = status.clone();
+        let guest_token = guest_token.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, status, &guest_token).await;
+        });
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    status: SharedStatus,
+    guest_token: &str,
+) -> std::io::Result<()> {
+    let from_loopback = stream.peer_addr()?.ip().is_loopback();
+    let head = tokio::time::timeout(READ_TIMEOUT, read_request_head(&mut stream))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+
+    let route = parse_request_path(&head).map(|path| route(path, guest_token, from_loopback));
+    let (code, content_type, body) = match route {
+        Some(Route::Index) => ("200 OK", "text/html; charset=utf-8", INDEX_HTML.to_string()),
+        Some(Route::Status) => (
+            "200 OK",
+            "application/json; charset=utf-8",
+            serde_json::to_string(&status.get()).unwrap_or_else(|_| "{}".to_string()),
+        ),
+        Some(Route::GuestIndex) => ("200 OK", "text/html; charset=utf-8", GUEST_HTML.to_string()),
+        Some(Route::GuestStatus) => (
+            "200 OK",
+            "application/json; charset=utf-8",
+            serde_json::to_string(&GuestSnapshot::from_status(&status.get()))
+                .unwrap_or_else(|_| "{}".to_string()),
+        ),
+        Some(Route::Forbidden) => (
+            "403 Forbidden",
+            "text/plain; charset=utf-8",
+            "forbidden".to_string(),
+        ),
+        Some(Route::NotFound) => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "not found".to_string(),
+        ),
+        None => (
+            "400 Bad Request",
+            "text/plain; charset=utf-8",
+            "bad request".to_string(),
+        ),
+    };
+
+    write_response(&mut stream, code, content_type, &body).await
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    code: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {code}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// 在本机临时端口打开一个回显页，用户用浏览器访问后返回请求中的 User-Agent。
+pub async fn detect_user_agent() -> Result<String, String> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("检测 UA：监听失败：{e}"))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| format!("检测 UA：监听失败：{e}"))?;
+    crate::platform::open_url(&format!("http://{addr}/"))
+        .map_err(|e| format!("检测 UA：无法打开浏览器：{e}"))?;
+
+    tokio::time::timeout(DETECT_UA_TIMEOUT, accept_user_agent(listener))
+        .await
+        .map_err(|_| "检测 UA：等待浏览器访问超时".to_string())?
+}
+
+async fn accept_user_agent(listener: TcpListener) -> Result<String, String> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("检测 UA：连接失败：{e}"))?;
+        let Ok(head) = read_request_head(&mut stream).await else {
+            continue;
+        };
+
+        // 浏览器会顺带请求 favicon 等资源，只认首页请求
+        let user_agent = (parse_request_path(&head) == Some("/"))
+            .then(|| parse_header(&head, "user-agent"))
+            .flatten()
+            .map(str::to_string);
+        let Some(user_agent) = user_agent else {
+            let _ = write_response(
+                &mut stream,
+                "404 Not Found",
+                "text/plain; charset=utf-8",
+                "not found",
+            )
+            .await;
+            continue;
+        };
+
+        let body = DETECT_UA_HTML.replace("{{USER_AGENT}}", &escape_html(&user_agent));
+        let _ = write_response(&mut stream, "200 OK", "text/html; charset=utf-8", &body).await;
+        return Ok(user_agent);
+    }
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buffer = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+
+    while buffer.len() < MAX_REQUEST_HEAD {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+            break;
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+/// 只接受 GET 请求，返回去掉查询串的路径。
+fn parse_request_path(head: &str) -> Option<&str> {
+    let request_line = head.lines().next()?;
+    let mut parts = request_line.split_whitespace();
+
+    if parts.next()? != "GET" {
+        return None;
+    }
+
+    let target = parts.next()?;
+    Some(target.split('?').next().unwrap_or(target))
+}
+
+/// 按名称（不区分大小写）取请求头的值。
+fn parse_header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim())
+            .filter(|value| !value.is_empty())
+    })
+}
+
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        GuestSnapshot, Route, StatusSnapshot, escape_html, parse_header, parse_request_path, route,
+    };
+
+    #[test]
+    fn parses_get_path_without_query() {
+        let head = "GET /api/status?t=1 HTTP/1.1\r\nHost: x\r\n\r\n";
+        assert_eq!(parse_request_path(head), Some("/api/status"));
+    }
+
+    #[test]
+    fn rejects_non_get_requests() {
+        let head = "POST /api/status HTTP/1.1\r\n\r\n";
+        assert_eq!(parse_request_path(head), None);
+    }
+
+    #[test]
+    fn rejects_empty_request() {
+        assert_eq!(parse_request_path(""), None);
+    }
+
+    #[test]
+    fn reads_headers_case_insensitively() {
+        let head = "GET / HTTP/1.1\r\nHost: x\r\nuser-agent: Mozilla/5.0 (X11)\r\n\r\n";
+        assert_eq!(parse_header(head, "User-Agent"), Some("Mozilla/5.0 (X11)"));
+        assert_eq!(parse_header(head, "Cookie"), None);
+    }
+
+    #[test]
+    fn guest_routes_require_the_current_token() {
+        assert_eq!(route("/guest/abc", "abc", false), Route::GuestIndex);
+        assert_eq!(route("/api/guest/abc", "abc", false), Route::GuestStatus);
+        assert_eq!(route("/guest/abd", "abc", false), Route::NotFound);
+        assert_eq!(route("/guest/", "", false), Route::NotFound);
+        assert_eq!(route("/api/status", "abc", true), Route::Status);
+    }
+
+    #[test]
+    fn full_routes_are_local_only_once_guest_link_is_enabled() {
+        assert_eq!(route("/", "abc", false), Route::Forbidden);
+        assert_eq!(route("/api/status", "abc", false), Route::Forbidden);
+        assert_eq!(route("/", "abc", true), Route::Index);
+        assert_eq!(route("/api/status", "", false), Route::Status);
+    }
+
+    #[test]
+    fn guest_snapshot_only_exposes_the_percentage() {
+        let status = StatusSnapshot {
+            title: "Pro".to_string(),
+            value: "12.34".to_string(),
+            remaining: Some(12.34),
+            total: Some(50.0),
+            ratio: 0.2468,
+            updated_at: Some(1_700_000_000),
+            ..StatusSnapshot::default()
+        };
+
+        assert_eq!(
+            GuestSnapshot::from_status(&status),
+            GuestSnapshot {
+                percent: 25,
+                updated_at: Some(1_700_000_000),
+                ok: true,
+            }
+        );
+        let json = serde_json::to_string(&GuestSnapshot::from_status(&status)).expect("json");
+        assert!(!json.contains("12.34") && !json.contains("Pro"));
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">&"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use iced::window::{self, Level};
+use iced::{Point, Size, Task};
+
+use crate::platform;
+
+/// 窗口的用途；设置页目前仍在悬浮球窗口内切换，独立窗口在此添加新的角色。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowRole {
+    Ball,
+    /// 中键打开的订阅总览，紧挨悬浮球显示。
+    Grid,
+}
+
+impl WindowRole {
+    /// 新建该角色窗口时的初始设置；没有保存的位置时由系统决定。
+    fn settings(self, size: Size, position: Option<Point>) -> window::Settings {
+        match self {
+            WindowRole::Ball => window::Settings {
+                size,
+                position: position.map_or(window::Position::Default, window::Position::Specific),
+                decorations: false,
+                transparent: true,
+                resizable: false,
+                level: Level::AlwaysOnTop,
+                ..window::Settings::default()
+            },
+            WindowRole::Grid => window::Settings {
+                size,
+                position: position.map_or(window::Position::Centered, window::Position::Specific),
+                decorations: false,
+                transparent: true,
+                resizable: false,
+                level: Level::AlwaysOnTop,
+                ..window::Settings::default()
+            },
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            WindowRole::Ball => "RightCode Floating Ball",
+            WindowRole::Grid => "订阅总览",
+        }
+    }
+}
+
+/// 窗口的尺寸与形状，由 `WindowManager::apply_layout` 统一应用。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowLayout {
+    pub size: Size,
+    /// 裁剪为圆形（悬浮球），否则为矩形。
+    pub round: bool,
+}
+
+/// 记录每个 `window::Id` 的角色，视图、事件与窗口操作都按角色路由。
+#[derive(Debug, Default)]
+pub struct WindowManager {
+    roles: HashMap<window::Id, WindowRole>,
+}
+
+impl WindowManager {
+    /// 打开一个新窗口并登记角色，返回的任务在窗口创建完成后给出其 id。
+    pub fn open(
+        &mut self,
+        role: WindowRole,
+        size: Size,
+        position: Option<Point>,
+    ) -> Task<window::Id> {
+        let (id, task) = window::open(role.settings(size, position));
+        self.roles.insert(id, role);
+        task
+    }
+
+    /// 窗口关闭后注销，返回它原来的角色。
+    pub fn remove(&mut self, id: window::Id) -> Option<WindowRole> {
+        self.roles.remove(&id)
+    }
+
+    pub fn role(&self, id: window::Id) -> Option<WindowRole> {
+        self.roles.get(&id).copied()
+    }
+
+    pub fn find(&self, role: WindowRole) -> Option<window::Id> {
+        self.roles
+            .iter()
+            .find_map(|(id, r)| (*r == role).then_some(*id))
+    }
+
+    pub fn ball(&self) -> Option<window::Id> {
+        self.find(WindowRole::Ball)
+    }
+
+    /// 调整大小与窗口区域；窗口未打开时不做任何事。
+    pub fn apply_layout<T: Send + 'static>(
+        &self,
+        role: WindowRole,
+        layout: WindowLayout,
+    ) -> Task<T> {
+        let Some(id) = self.find(role) else {
+            return Task::none();
+        };
+
+        let round = layout.round;
+        window::resize(id, layout.size).chain(
+            window::run_with_handle(id, move |handle| {
+                platform::set_round_window_region(handle, round);
+            })
+            .discard(),
+        )
+    }
+}